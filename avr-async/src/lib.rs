@@ -70,6 +70,8 @@ pub mod task;
 pub mod time;
 #[cfg(feature = "twi")]
 pub mod twi;
+#[cfg(feature = "twi")]
+pub mod twi2;
 
 pub use avr_device::interrupt::CriticalSection;
 pub use sync_unsafe_cell::SyncUnsafeCell;
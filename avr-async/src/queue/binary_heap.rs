@@ -0,0 +1,182 @@
+use core::mem::MaybeUninit;
+
+/// Fixed-capacity max-heap: [`BinaryHeap::pop`] always returns the greatest value.
+///
+/// Wrap values in [`core::cmp::Reverse`] to get the smallest first, e.g. for deadlines.
+pub struct BinaryHeap<T: Ord, const N: usize> {
+    len: usize,
+    data: [MaybeUninit<T>; N],
+}
+
+impl<T: Ord, const N: usize> BinaryHeap<T, N> {
+    const INIT: MaybeUninit<T> = MaybeUninit::uninit();
+
+    #[inline(always)]
+    pub const fn new() -> Self {
+        Self {
+            len: 0,
+            data: [Self::INIT; N],
+        }
+    }
+
+    #[inline(always)]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    #[inline(always)]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline(always)]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    #[inline(always)]
+    pub const fn is_full(&self) -> bool {
+        self.len == N
+    }
+
+    #[inline]
+    pub fn peek(&self) -> Option<&T> {
+        self.as_slice().first()
+    }
+
+    pub fn push(&mut self, val: T) -> Result<(), T> {
+        if self.len == N {
+            return Err(val);
+        }
+
+        unsafe {
+            self.data.get_unchecked_mut(self.len).write(val);
+            self.len += 1;
+            self.sift_up(self.len - 1);
+        }
+        Ok(())
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        if self.len == 0 {
+            return None;
+        }
+
+        self.len -= 1;
+        unsafe {
+            let last = self.data.get_unchecked(self.len).as_ptr().read();
+            if self.len == 0 {
+                Some(last)
+            } else {
+                let top = core::mem::replace(self.data.get_unchecked_mut(0).assume_init_mut(), last);
+                self.sift_down(0);
+                Some(top)
+            }
+        }
+    }
+
+    #[inline]
+    pub fn clear(&mut self) {
+        let len = self.len;
+        // Forget the values first, a panicking destructor must not cause a double drop.
+        self.len = 0;
+        unsafe {
+            core::ptr::drop_in_place(core::slice::from_raw_parts_mut(
+                self.data.as_mut_ptr() as *mut T,
+                len,
+            ))
+        };
+    }
+
+    /// The values in heap order, which is not sorted.
+    #[inline(always)]
+    pub fn as_slice(&self) -> &[T] {
+        unsafe { core::slice::from_raw_parts(self.data.as_ptr() as *const T, self.len) }
+    }
+
+    #[inline(always)]
+    pub fn iter(&self) -> core::slice::Iter<T> {
+        self.as_slice().iter()
+    }
+
+    /// Moves the value at `pos` up until its parent is greater or equal.
+    unsafe fn sift_up(&mut self, mut pos: usize) {
+        let base = self.data.as_mut_ptr() as *mut T;
+        let hole = base.add(pos).read();
+
+        while pos > 0 {
+            let parent = (pos - 1) / 2;
+            if hole <= *base.add(parent) {
+                break;
+            }
+            base.add(pos).write(base.add(parent).read());
+            pos = parent;
+        }
+
+        base.add(pos).write(hole);
+    }
+
+    /// Moves the value at `pos` down until both children are lower or equal.
+    unsafe fn sift_down(&mut self, mut pos: usize) {
+        let base = self.data.as_mut_ptr() as *mut T;
+        let hole = base.add(pos).read();
+
+        loop {
+            let mut child = 2 * pos + 1;
+            if child >= self.len {
+                break;
+            }
+            if child + 1 < self.len && *base.add(child) < *base.add(child + 1) {
+                child += 1;
+            }
+            if hole >= *base.add(child) {
+                break;
+            }
+            base.add(pos).write(base.add(child).read());
+            pos = child;
+        }
+
+        base.add(pos).write(hole);
+    }
+}
+
+impl<T: Ord, const N: usize> Drop for BinaryHeap<T, N> {
+    #[inline]
+    fn drop(&mut self) {
+        self.clear()
+    }
+}
+
+impl<T: Ord, const N: usize> Default for BinaryHeap<T, N> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord + Clone, const N: usize> Clone for BinaryHeap<T, N> {
+    fn clone(&self) -> Self {
+        let mut heap = Self::new();
+        for (i, val) in self.iter().enumerate() {
+            heap.data[i].write(val.clone());
+            heap.len = i + 1;
+        }
+        heap
+    }
+}
+
+impl<T: Ord + core::fmt::Debug, const N: usize> core::fmt::Debug for BinaryHeap<T, N> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<'a, T: Ord, const N: usize> IntoIterator for &'a BinaryHeap<T, N> {
+    type Item = &'a T;
+    type IntoIter = core::slice::Iter<'a, T>;
+
+    #[inline(always)]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
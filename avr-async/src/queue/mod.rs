@@ -1,3 +1,8 @@
+pub mod binary_heap;
+pub mod spsc;
+
+pub use binary_heap::BinaryHeap;
+
 use core::{cell::UnsafeCell, mem::MaybeUninit};
 
 pub struct Queue<T, const N: usize> {
@@ -25,7 +30,7 @@ impl<T, const N: usize> Queue<T, N> {
     pub fn len(&self) -> usize {
         self.bounds
             .as_ref()
-            .map(|&(head, tail)| (tail.wrapping_sub(head).wrapping_add(N) % N) + 1)
+            .map(|&(head, tail)| Self::wrap(tail.wrapping_sub(head).wrapping_add(N)) + 1)
             .unwrap_or(0)
     }
 
@@ -52,6 +57,75 @@ impl<T, const N: usize> Queue<T, N> {
         unsafe { self.inner_dequeue() }
     }
 
+    #[inline(always)]
+    pub fn push_back(&mut self, val: T) -> Result<(), T> {
+        self.enqueue(val)
+    }
+
+    #[inline(always)]
+    pub fn pop_front(&mut self) -> Option<T> {
+        self.dequeue()
+    }
+
+    #[inline]
+    pub fn push_front(&mut self, val: T) -> Result<(), T> {
+        unsafe { self.inner_push_front(val) }
+    }
+
+    #[inline]
+    pub fn pop_back(&mut self) -> Option<T> {
+        unsafe { self.inner_pop_back() }
+    }
+
+    #[inline]
+    pub fn peek(&self) -> Option<&T> {
+        self.bounds
+            .map(|(head, _)| unsafe { &*(self.buffer.get_unchecked(head).get() as *const T) })
+    }
+
+    #[inline]
+    pub fn peek_mut(&mut self) -> Option<&mut T> {
+        self.bounds
+            .map(|(head, _)| unsafe { &mut *(self.buffer.get_unchecked(head).get() as *mut T) })
+    }
+
+    #[inline]
+    pub fn peek_back(&self) -> Option<&T> {
+        self.bounds
+            .map(|(_, tail)| unsafe { &*(self.buffer.get_unchecked(tail).get() as *const T) })
+    }
+
+    #[inline]
+    pub fn peek_back_mut(&mut self) -> Option<&mut T> {
+        self.bounds
+            .map(|(_, tail)| unsafe { &mut *(self.buffer.get_unchecked(tail).get() as *mut T) })
+    }
+
+    /// Removes every value from the front, dropping the ones the returned iterator doesn't yield.
+    #[inline(always)]
+    pub fn drain(&mut self) -> Drain<T, N> {
+        Drain { q: self }
+    }
+
+    /// Keeps only the values for which `f` returns `true`, preserving their order.
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&mut T) -> bool,
+    {
+        for _ in 0..self.len() {
+            let mut val = unsafe { self.inner_dequeue().unwrap_unchecked() };
+            if f(&mut val) {
+                // A slot has just been freed, this can't fail.
+                let _ = unsafe { self.inner_enqueue(val) };
+            }
+        }
+    }
+
+    #[inline]
+    pub fn clear(&mut self) {
+        while self.dequeue().is_some() {}
+    }
+
     #[inline(always)]
     pub fn iter(&self) -> Iter<T, N> {
         self.into_iter()
@@ -87,12 +161,36 @@ impl<T, const N: usize> Queue<T, N> {
         }
     }
 
+    unsafe fn inner_push_front(&mut self, val: T) -> Result<(), T> {
+        match match self.bounds {
+            Some((head, tail)) => {
+                let next_head = Self::dec(head);
+
+                if tail == next_head {
+                    Err(())
+                } else {
+                    Ok((next_head, tail))
+                }
+            }
+            None => Ok((0, 0)),
+        } {
+            Ok((head, tail)) => {
+                self.bounds = Some((head, tail));
+                self.buffer
+                    .get_unchecked(head)
+                    .get()
+                    .write(MaybeUninit::new(val));
+                Ok(())
+            }
+            Err(()) => Err(val),
+        }
+    }
+
     unsafe fn inner_dequeue(&mut self) -> Option<T> {
         match self.bounds {
             Some((head, tail)) => {
                 let v = (self.buffer.get_unchecked(head).get() as *const T).read();
-                let len = tail.wrapping_sub(head).wrapping_add(N) % N;
-                if len == 0 {
+                if head == tail {
                     self.bounds = None;
                 } else {
                     self.bounds = Some((Self::inc(head), tail));
@@ -103,9 +201,77 @@ impl<T, const N: usize> Queue<T, N> {
         }
     }
 
+    unsafe fn inner_pop_back(&mut self) -> Option<T> {
+        match self.bounds {
+            Some((head, tail)) => {
+                let v = (self.buffer.get_unchecked(tail).get() as *const T).read();
+                if head == tail {
+                    self.bounds = None;
+                } else {
+                    self.bounds = Some((head, Self::dec(tail)));
+                }
+                Some(v)
+            }
+            None => None,
+        }
+    }
+
+    /// Reduces an index in `0..2 * N`, masking instead of dividing when `N` is a power of two.
+    #[inline(always)]
+    const fn wrap(val: usize) -> usize {
+        if N.is_power_of_two() {
+            val & (N - 1)
+        } else {
+            val % N
+        }
+    }
+
     #[inline(always)]
     const fn inc(val: usize) -> usize {
-        (val + 1) % N
+        Self::wrap(val + 1)
+    }
+
+    #[inline(always)]
+    const fn dec(val: usize) -> usize {
+        Self::wrap(val + N - 1)
+    }
+}
+
+impl<T: Copy, const N: usize> Queue<T, N> {
+    /// Appends every value of `slice`, or nothing at all if they don't fit.
+    pub fn extend_from_slice(&mut self, slice: &[T]) -> Result<(), ()> {
+        if N - self.len() < slice.len() {
+            return Err(());
+        }
+
+        for &val in slice {
+            let _ = unsafe { self.inner_enqueue(val) };
+        }
+
+        Ok(())
+    }
+}
+
+impl<T, const N: usize> Drop for Queue<T, N> {
+    #[inline]
+    fn drop(&mut self) {
+        self.clear()
+    }
+}
+
+impl<T: Clone, const N: usize> Clone for Queue<T, N> {
+    fn clone(&self) -> Self {
+        let mut q = Self::new();
+        for val in self {
+            let _ = unsafe { q.inner_enqueue(val.clone()) };
+        }
+        q
+    }
+}
+
+impl<T: core::fmt::Debug, const N: usize> core::fmt::Debug for Queue<T, N> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
     }
 }
 
@@ -142,7 +308,7 @@ impl<'a, T, const N: usize> Iterator for Iter<'a, T, N> {
             None
         } else {
             let head = self.q.bounds.unwrap().0;
-            let i = head.wrapping_add(self.index) % N;
+            let i = Queue::<T, N>::wrap(head.wrapping_add(self.index));
             self.index += 1;
             Some(unsafe { &*(self.q.buffer.get_unchecked(i).get() as *const T) })
         }
@@ -173,13 +339,48 @@ impl<'a, T, const N: usize> Iterator for IterMut<'a, T, N> {
             None
         } else {
             let head = self.q.bounds.unwrap().0;
-            let i = head.wrapping_add(self.index) % N;
+            let i = Queue::<T, N>::wrap(head.wrapping_add(self.index));
             self.index += 1;
             Some(unsafe { &mut *self.q.buffer.get_unchecked_mut(i).get_mut().as_mut_ptr() })
         }
     }
 }
 
+pub struct Drain<'a, T, const N: usize> {
+    q: &'a mut Queue<T, N>,
+}
+
+impl<'a, T, const N: usize> Iterator for Drain<'a, T, N> {
+    type Item = T;
+
+    #[inline(always)]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.q.dequeue()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.q.len();
+        (len, Some(len))
+    }
+}
+
+impl<'a, T, const N: usize> DoubleEndedIterator for Drain<'a, T, N> {
+    #[inline(always)]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.q.pop_back()
+    }
+}
+
+impl<'a, T, const N: usize> ExactSizeIterator for Drain<'a, T, N> {}
+
+impl<'a, T, const N: usize> Drop for Drain<'a, T, N> {
+    #[inline]
+    fn drop(&mut self) {
+        self.q.clear()
+    }
+}
+
 impl<'a, T, const N: usize> IntoIterator for &'a Queue<T, N> {
     type Item = &'a T;
 
@@ -0,0 +1,231 @@
+use core::{
+    cell::UnsafeCell,
+    marker::PhantomData,
+    mem::MaybeUninit,
+    sync::atomic::{AtomicU8, Ordering},
+};
+
+use avr_device::interrupt::CriticalSection;
+
+use crate::runtime::Ready;
+
+/// Single-producer/single-consumer ring buffer.
+///
+/// `head` is only written by the [`Consumer`] and `tail` only by the [`Producer`]. Both indices
+/// are single bytes, so loads and stores are atomic on AVR and one end can live in an interrupt
+/// handler while the other runs in a task without any critical section.
+///
+/// One slot is kept empty to tell a full queue from an empty one, so `N` slots hold up to `N - 1`
+/// values. `N` must be between 2 and 256.
+pub struct Queue<T, const N: usize> {
+    head: AtomicU8,
+    tail: AtomicU8,
+    buffer: [UnsafeCell<MaybeUninit<T>>; N],
+}
+
+unsafe impl<T: Send, const N: usize> Sync for Queue<T, N> {}
+
+impl<T, const N: usize> Queue<T, N> {
+    #[allow(clippy::declare_interior_mutable_const)]
+    const INIT: UnsafeCell<MaybeUninit<T>> = UnsafeCell::new(MaybeUninit::uninit());
+
+    #[inline(always)]
+    pub const fn new() -> Self {
+        crate::sealed::greater_than::<N, 1>();
+        crate::sealed::less_than_eq::<N, 256>();
+
+        Self {
+            head: AtomicU8::new(0),
+            tail: AtomicU8::new(0),
+            buffer: [Self::INIT; N],
+        }
+    }
+
+    #[inline(always)]
+    pub const fn capacity(&self) -> usize {
+        N - 1
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        let head = self.head.load(Ordering::Acquire) as usize;
+        let tail = self.tail.load(Ordering::Acquire) as usize;
+
+        if tail >= head {
+            tail - head
+        } else {
+            N - head + tail
+        }
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.head.load(Ordering::Acquire) == self.tail.load(Ordering::Acquire)
+    }
+
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        Self::inc(self.tail.load(Ordering::Acquire)) == self.head.load(Ordering::Acquire)
+    }
+
+    #[inline(always)]
+    pub fn split(&mut self) -> (Producer<T, N>, Consumer<T, N>) {
+        (
+            Producer {
+                q: self,
+                _not_sync: PhantomData,
+            },
+            Consumer {
+                q: self,
+                _not_sync: PhantomData,
+            },
+        )
+    }
+
+    #[inline(always)]
+    const fn inc(val: u8) -> u8 {
+        if val as usize + 1 == N {
+            0
+        } else {
+            val + 1
+        }
+    }
+
+    unsafe fn inner_enqueue(&self, val: T) -> Result<(), T> {
+        let tail = self.tail.load(Ordering::Relaxed);
+        let next = Self::inc(tail);
+
+        if next == self.head.load(Ordering::Acquire) {
+            Err(val)
+        } else {
+            self.buffer
+                .get_unchecked(tail as usize)
+                .get()
+                .write(MaybeUninit::new(val));
+            self.tail.store(next, Ordering::Release);
+            Ok(())
+        }
+    }
+
+    unsafe fn inner_dequeue(&self) -> Option<T> {
+        let head = self.head.load(Ordering::Relaxed);
+
+        if head == self.tail.load(Ordering::Acquire) {
+            None
+        } else {
+            let v = (self.buffer.get_unchecked(head as usize).get() as *const T).read();
+            self.head.store(Self::inc(head), Ordering::Release);
+            Some(v)
+        }
+    }
+
+    unsafe fn inner_peek(&self) -> Option<&T> {
+        let head = self.head.load(Ordering::Relaxed);
+
+        if head == self.tail.load(Ordering::Acquire) {
+            None
+        } else {
+            Some(&*(self.buffer.get_unchecked(head as usize).get() as *const T))
+        }
+    }
+}
+
+impl<T, const N: usize> Default for Queue<T, N> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const N: usize> Drop for Queue<T, N> {
+    fn drop(&mut self) {
+        while unsafe { self.inner_dequeue() }.is_some() {}
+    }
+}
+
+impl<T, const N: usize> Ready for Queue<T, N> {
+    #[inline]
+    fn is_ready(&self, _: &CriticalSection) -> bool {
+        !self.is_empty()
+    }
+}
+
+pub struct Producer<'a, T, const N: usize> {
+    q: &'a Queue<T, N>,
+    _not_sync: PhantomData<*const ()>,
+}
+
+unsafe impl<'a, T: Send, const N: usize> Send for Producer<'a, T, N> {}
+
+impl<'a, T, const N: usize> Producer<'a, T, N> {
+    #[inline]
+    pub fn enqueue(&mut self, val: T) -> Result<(), T> {
+        unsafe { self.q.inner_enqueue(val) }
+    }
+
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.q.len()
+    }
+
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.q.is_empty()
+    }
+
+    #[inline(always)]
+    pub fn is_full(&self) -> bool {
+        self.q.is_full()
+    }
+
+    #[inline(always)]
+    pub const fn capacity(&self) -> usize {
+        N - 1
+    }
+}
+
+pub struct Consumer<'a, T, const N: usize> {
+    q: &'a Queue<T, N>,
+    _not_sync: PhantomData<*const ()>,
+}
+
+unsafe impl<'a, T: Send, const N: usize> Send for Consumer<'a, T, N> {}
+
+impl<'a, T, const N: usize> Consumer<'a, T, N> {
+    #[inline]
+    pub fn dequeue(&mut self) -> Option<T> {
+        unsafe { self.q.inner_dequeue() }
+    }
+
+    #[inline]
+    pub fn peek(&self) -> Option<&T> {
+        unsafe { self.q.inner_peek() }
+    }
+
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.q.len()
+    }
+
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.q.is_empty()
+    }
+
+    #[inline(always)]
+    pub fn is_full(&self) -> bool {
+        self.q.is_full()
+    }
+
+    #[inline(always)]
+    pub const fn capacity(&self) -> usize {
+        N - 1
+    }
+}
+
+impl<'a, T, const N: usize> Ready for Consumer<'a, T, N> {
+    #[inline]
+    fn is_ready(&self, _: &CriticalSection) -> bool {
+        !self.q.is_empty()
+    }
+}
@@ -1,9 +1,13 @@
+mod pool;
+
 use core::{
     borrow::{Borrow, BorrowMut},
     mem::MaybeUninit,
     ops::{Deref, DerefMut},
 };
 
+pub use pool::{Alloc, Pool, PoolBox, PoolSlab};
+
 pub trait Slabbed {
     type InnerType;
 }
@@ -29,6 +33,11 @@ impl<T: Slabbed> Slab<T> {
         mem.write(value);
         unsafe { SlabBox::from_ptr(MaybeUninit::as_mut_ptr(mem)) }
     }
+
+    #[inline(always)]
+    pub(crate) const fn into_raw(self) -> *mut MaybeUninit<T::InnerType> {
+        self.mem
+    }
 }
 
 impl<T> SlabBox<T> {
@@ -0,0 +1,257 @@
+use core::{
+    borrow::{Borrow, BorrowMut},
+    cell::UnsafeCell,
+    future::Future,
+    mem::MaybeUninit,
+    ops::{Deref, DerefMut},
+    pin::Pin,
+    ptr::{addr_of_mut, NonNull},
+    task::Poll,
+};
+
+use avr_device::interrupt::{self, CriticalSection};
+
+use super::{Slab, Slabbed};
+use crate::runtime::Ready;
+
+pub struct PoolSlab<T, const N: usize> {
+    blocks: [UnsafeCell<MaybeUninit<T>>; N],
+    used: [bool; N],
+    free: usize,
+    waiting: usize,
+}
+
+/// Fixed-capacity pool of `N` blocks of `T`, declared through `#[memory]` like any other slab.
+///
+/// Blocks are returned to the pool when their [`PoolBox`] is dropped, so the same memory can be
+/// recycled between tasks and interrupt handlers.
+pub struct Pool<T, const N: usize>(NonNull<PoolSlab<T, N>>);
+
+impl<T, const N: usize> Slabbed for Pool<T, N> {
+    type InnerType = PoolSlab<T, N>;
+}
+
+impl<T, const N: usize> Pool<T, N> {
+    pub fn new(slab: Slab<Self>) -> Self {
+        let mem = slab.into_raw() as *mut PoolSlab<T, N>;
+
+        // Blocks stay uninitialized, only the bookkeeping is written to avoid moving the whole
+        // pool through the stack.
+        unsafe {
+            addr_of_mut!((*mem).used).write([false; N]);
+            addr_of_mut!((*mem).free).write(N);
+            addr_of_mut!((*mem).waiting).write(0);
+            Self(NonNull::new_unchecked(mem))
+        }
+    }
+
+    #[allow(clippy::mut_from_ref)]
+    #[inline(always)]
+    fn inner(&self) -> &mut PoolSlab<T, N> {
+        unsafe { &mut *(self.0.as_ptr()) }
+    }
+
+    #[inline(always)]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    #[inline]
+    pub fn available(&self) -> usize {
+        interrupt::free(|_cs| self.inner().free)
+    }
+
+    pub fn try_alloc(&self, value: T) -> Result<PoolBox<T, N>, T> {
+        let index = interrupt::free(|_cs| {
+            let inner = self.inner();
+
+            if inner.free == 0 {
+                return None;
+            }
+
+            let index = inner.used.iter().position(|&used| !used)?;
+            inner.used[index] = true;
+            inner.free -= 1;
+            Some(index)
+        });
+
+        match index {
+            Some(index) => unsafe {
+                let block = self.inner().blocks.get_unchecked(index).get();
+                (*block).write(value);
+                Ok(PoolBox {
+                    pool: self.clone(),
+                    block: (*block).as_mut_ptr(),
+                    index,
+                })
+            },
+            None => Err(value),
+        }
+    }
+
+    /// Waits for a free block when the pool is exhausted.
+    #[inline(always)]
+    pub fn alloc(&self, value: T) -> Alloc<T, N> {
+        Alloc {
+            pool: self.clone(),
+            value: Some(value),
+            waiting: false,
+        }
+    }
+
+    fn release(&self, index: usize) {
+        interrupt::free(|_cs| {
+            let inner = self.inner();
+            inner.used[index] = false;
+            inner.free += 1;
+
+            if inner.waiting != 0 {
+                unsafe { crate::executor::wake() };
+            }
+        });
+    }
+}
+
+unsafe impl<T: Send, const N: usize> Send for Pool<T, N> {}
+unsafe impl<T: Send, const N: usize> Sync for Pool<T, N> {}
+
+impl<T, const N: usize> Clone for Pool<T, N> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        Self(self.0)
+    }
+}
+
+impl<T, const N: usize> Copy for Pool<T, N> {}
+
+impl<T, const N: usize> Ready for Pool<T, N> {
+    #[inline]
+    fn is_ready(&self, _: &CriticalSection) -> bool {
+        let inner = self.inner();
+        inner.waiting != 0 && inner.free != 0
+    }
+}
+
+pub struct Alloc<T, const N: usize> {
+    pool: Pool<T, N>,
+    value: Option<T>,
+    waiting: bool,
+}
+
+impl<T, const N: usize> Future for Alloc<T, N> {
+    type Output = PoolBox<T, N>;
+
+    fn poll(self: Pin<&mut Self>, _cx: &mut core::task::Context<'_>) -> Poll<Self::Output> {
+        let this = unsafe { Pin::get_unchecked_mut(self) };
+
+        match this.pool.try_alloc(this.value.take().unwrap()) {
+            Ok(b) => {
+                if this.waiting {
+                    this.waiting = false;
+                    interrupt::free(|_cs| this.pool.inner().waiting -= 1);
+                }
+                Poll::Ready(b)
+            }
+            Err(value) => {
+                this.value = Some(value);
+                if !this.waiting {
+                    this.waiting = true;
+                    interrupt::free(|_cs| this.pool.inner().waiting += 1);
+                }
+                Poll::Pending
+            }
+        }
+    }
+}
+
+impl<T, const N: usize> Drop for Alloc<T, N> {
+    #[inline]
+    fn drop(&mut self) {
+        if self.waiting {
+            interrupt::free(|_cs| self.pool.inner().waiting -= 1);
+        }
+    }
+}
+
+/// A block borrowed from a [`Pool`], given back when dropped.
+pub struct PoolBox<T, const N: usize> {
+    pool: Pool<T, N>,
+    block: *mut T,
+    index: usize,
+}
+
+unsafe impl<T: Send, const N: usize> Send for PoolBox<T, N> {}
+unsafe impl<T: Sync, const N: usize> Sync for PoolBox<T, N> {}
+
+impl<T, const N: usize> PoolBox<T, N> {
+    #[inline(always)]
+    pub const fn as_ptr(&self) -> *const T {
+        self.block
+    }
+
+    #[inline(always)]
+    pub const fn as_ptr_mut(&mut self) -> *mut T {
+        self.block
+    }
+
+    /// Moves the value out and gives the block back to the pool.
+    #[inline]
+    pub fn into_inner(this: Self) -> T {
+        let value = unsafe { this.block.read() };
+        this.pool.release(this.index);
+        core::mem::forget(this);
+        value
+    }
+}
+
+impl<T, const N: usize> Drop for PoolBox<T, N> {
+    #[inline]
+    fn drop(&mut self) {
+        unsafe { core::ptr::drop_in_place(self.block) };
+        self.pool.release(self.index);
+    }
+}
+
+impl<T, const N: usize> Deref for PoolBox<T, N> {
+    type Target = T;
+
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        unsafe { &*self.block }
+    }
+}
+
+impl<T, const N: usize> DerefMut for PoolBox<T, N> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        unsafe { &mut *self.block }
+    }
+}
+
+impl<T, const N: usize> Borrow<T> for PoolBox<T, N> {
+    #[inline(always)]
+    fn borrow(&self) -> &T {
+        Deref::deref(self)
+    }
+}
+
+impl<T, const N: usize> BorrowMut<T> for PoolBox<T, N> {
+    #[inline(always)]
+    fn borrow_mut(&mut self) -> &mut T {
+        DerefMut::deref_mut(self)
+    }
+}
+
+impl<T, const N: usize> AsRef<T> for PoolBox<T, N> {
+    #[inline(always)]
+    fn as_ref(&self) -> &T {
+        Deref::deref(self)
+    }
+}
+
+impl<T, const N: usize> AsMut<T> for PoolBox<T, N> {
+    #[inline(always)]
+    fn as_mut(&mut self) -> &mut T {
+        DerefMut::deref_mut(self)
+    }
+}
@@ -1,25 +1,60 @@
-use core::{borrow::Borrow, ops::Deref, ptr::NonNull};
+use core::{borrow::Borrow, marker::PhantomData, ops::Deref, ptr::NonNull};
+
+use avr_device::interrupt;
 
 use crate::slab::{Slab, SlabBox, Slabbed};
 
+/// How an [`Arc`] updates its reference count.
+pub trait Counting {
+    fn update<R>(f: impl FnOnce() -> R) -> R;
+}
+
+/// Updates the count as is, clones must not be created or dropped from interrupt handlers.
+pub struct Local;
+
+impl Counting for Local {
+    #[inline(always)]
+    fn update<R>(f: impl FnOnce() -> R) -> R {
+        f()
+    }
+}
+
+/// Updates the count inside a critical section.
+pub struct Isr;
+
+impl Counting for Isr {
+    #[inline(always)]
+    fn update<R>(f: impl FnOnce() -> R) -> R {
+        interrupt::free(|_cs| f())
+    }
+}
+
 pub struct ArcSlab<T> {
     count: usize,
     value: T,
 }
 
-pub struct Arc<T>(NonNull<ArcSlab<T>>);
+pub struct Arc<T, C: Counting = Local>(NonNull<ArcSlab<T>>, PhantomData<C>);
 
-impl<T> Slabbed for Arc<T> {
+/// Like [`Arc`], but the reference count is updated inside a critical section, so clones can be
+/// created and dropped from interrupt handlers while tasks do the same.
+pub type IsrArc<T> = Arc<T, Isr>;
+
+impl<T, C: Counting> Slabbed for Arc<T, C> {
     type InnerType = ArcSlab<T>;
 }
 
-impl<T> Arc<T> {
+impl<T, C: Counting> Arc<T, C> {
     #[allow(clippy::not_unsafe_ptr_arg_deref)]
     #[inline(always)]
-    pub fn new(slab: Slab<Arc<T>>, value: T) -> Self {
-        Self(unsafe {
-            NonNull::new(SlabBox::leak(slab.get(ArcSlab { count: 1, value }))).unwrap_unchecked()
-        })
+    pub fn new(slab: Slab<Arc<T, C>>, value: T) -> Self {
+        Self(
+            unsafe {
+                NonNull::new(SlabBox::leak(slab.get(ArcSlab { count: 1, value })))
+                    .unwrap_unchecked()
+            },
+            PhantomData,
+        )
     }
 
     const fn inner(&self) -> &ArcSlab<T> {
@@ -31,11 +66,6 @@ impl<T> Arc<T> {
         unsafe { &mut *(self.0.as_ptr()) }
     }
 
-    #[inline]
-    const fn inc(&self) {
-        self.inner_mut().count += 1;
-    }
-
     #[inline]
     const fn value(&self) -> &T {
         &self.inner().value
@@ -54,13 +84,19 @@ impl<T> Arc<T> {
     }
 
     #[inline]
-    pub const fn strong_count(this: &Self) -> usize {
-        this.inner().count
+    pub fn strong_count(this: &Self) -> usize {
+        C::update(|| this.inner().count)
     }
 
     fn dec(&self) {
-        self.inner_mut().count -= 1;
-        if self.inner().count == 0 {
+        let last = C::update(|| {
+            let inner = self.inner_mut();
+            inner.count -= 1;
+            inner.count == 0
+        });
+
+        // Nobody else can reach the value anymore, the destructor can run with interrupts enabled.
+        if last {
             unsafe {
                 let v = &mut *core::ptr::addr_of_mut!(self.inner_mut().value);
                 core::ptr::drop_in_place(v);
@@ -69,25 +105,47 @@ impl<T> Arc<T> {
     }
 }
 
-unsafe impl<T: Sync + Send> Send for Arc<T> {}
-unsafe impl<T: Sync + Send> Sync for Arc<T> {}
+impl<T> Arc<T> {
+    #[inline]
+    const fn inc(&self) {
+        self.inner_mut().count += 1;
+    }
+}
+
+impl<T> IsrArc<T> {
+    #[inline]
+    fn inc(&self) {
+        Isr::update(|| self.inner_mut().count += 1);
+    }
+}
+
+unsafe impl<T: Sync + Send, C: Counting> Send for Arc<T, C> {}
+unsafe impl<T: Sync + Send, C: Counting> Sync for Arc<T, C> {}
 
 impl<T> const Clone for Arc<T> {
     #[inline]
     fn clone(&self) -> Self {
         self.inc();
-        Self(self.0)
+        Self(self.0, PhantomData)
+    }
+}
+
+impl<T> Clone for IsrArc<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        self.inc();
+        Self(self.0, PhantomData)
     }
 }
 
-impl<T> Drop for Arc<T> {
+impl<T, C: Counting> Drop for Arc<T, C> {
     #[inline]
     fn drop(&mut self) {
         self.dec()
     }
 }
 
-impl<T> Deref for Arc<T> {
+impl<T, C: Counting> Deref for Arc<T, C> {
     type Target = T;
 
     #[inline]
@@ -96,18 +154,18 @@ impl<T> Deref for Arc<T> {
     }
 }
 
-impl<T> AsRef<T> for Arc<T> {
+impl<T, C: Counting> AsRef<T> for Arc<T, C> {
     #[inline]
     fn as_ref(&self) -> &T {
         self.value()
     }
 }
 
-impl<T> Borrow<T> for Arc<T> {
+impl<T, C: Counting> Borrow<T> for Arc<T, C> {
     #[inline]
     fn borrow(&self) -> &T {
         self.value()
     }
 }
 
-impl<T> Unpin for Arc<T> {}
+impl<T, C: Counting> Unpin for Arc<T, C> {}
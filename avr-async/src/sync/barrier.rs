@@ -0,0 +1,127 @@
+use core::{cell::UnsafeCell, future::Future, pin::Pin, task::Poll};
+
+use avr_device::interrupt::CriticalSection;
+
+use crate::runtime::Ready;
+
+#[derive(Debug)]
+struct InnerBarrier {
+    n: usize,
+    arrived: usize,
+    generation: usize,
+    releasing: usize,
+}
+
+#[derive(Debug)]
+pub struct Barrier {
+    inner: UnsafeCell<InnerBarrier>,
+}
+
+impl Barrier {
+    /// A barrier created with `n == 0` behaves like one created with `n == 1`.
+    #[inline(always)]
+    pub const fn new(n: usize) -> Self {
+        Self {
+            inner: UnsafeCell::new(InnerBarrier {
+                n: if n == 0 { 1 } else { n },
+                arrived: 0,
+                generation: 0,
+                releasing: 0,
+            }),
+        }
+    }
+
+    #[inline(always)]
+    pub fn wait(&self) -> BarrierWait {
+        BarrierWait {
+            barrier: self,
+            state: State::Idle,
+        }
+    }
+
+    #[allow(clippy::mut_from_ref)]
+    #[inline(always)]
+    fn inner(&self) -> &mut InnerBarrier {
+        unsafe { &mut *(self.inner.get()) }
+    }
+}
+
+impl Ready for Barrier {
+    #[inline]
+    fn is_ready(&self, _: &CriticalSection) -> bool {
+        self.inner().releasing != 0
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BarrierWaitResult(bool);
+
+impl BarrierWaitResult {
+    /// Exactly one task per generation, the last one to arrive, is the leader.
+    #[inline(always)]
+    pub const fn is_leader(&self) -> bool {
+        self.0
+    }
+}
+
+enum State {
+    Idle,
+    Waiting(usize),
+    Done,
+}
+
+pub struct BarrierWait<'a> {
+    barrier: &'a Barrier,
+    state: State,
+}
+
+impl<'a> Future for BarrierWait<'a> {
+    type Output = BarrierWaitResult;
+
+    fn poll(mut self: Pin<&mut Self>, _cx: &mut core::task::Context<'_>) -> Poll<Self::Output> {
+        let barrier = self.barrier;
+        let inner = barrier.inner();
+
+        match self.state {
+            State::Idle => {
+                inner.arrived += 1;
+                if inner.arrived == inner.n {
+                    inner.arrived = 0;
+                    inner.generation = inner.generation.wrapping_add(1);
+                    inner.releasing = inner.n - 1;
+                    self.state = State::Done;
+                    if inner.releasing != 0 {
+                        unsafe { crate::executor::wake() };
+                    }
+                    Poll::Ready(BarrierWaitResult(true))
+                } else {
+                    self.state = State::Waiting(inner.generation);
+                    Poll::Pending
+                }
+            }
+            State::Waiting(generation) => {
+                if generation == inner.generation {
+                    Poll::Pending
+                } else {
+                    inner.releasing -= 1;
+                    self.state = State::Done;
+                    Poll::Ready(BarrierWaitResult(false))
+                }
+            }
+            State::Done => panic!("BarrierWait polled after completion"),
+        }
+    }
+}
+
+impl<'a> Drop for BarrierWait<'a> {
+    fn drop(&mut self) {
+        if let State::Waiting(generation) = self.state {
+            let inner = self.barrier.inner();
+            if generation == inner.generation {
+                inner.arrived -= 1;
+            } else {
+                inner.releasing -= 1;
+            }
+        }
+    }
+}
@@ -1,9 +1,18 @@
 pub mod arc;
+pub mod barrier;
 pub mod mutex;
+pub mod notify;
+pub mod pipe;
+pub mod priority_queue;
 pub mod queue;
 pub mod semaphore;
+pub(crate) mod waitlist;
 
-pub use arc::Arc;
+pub use arc::{Arc, Counting, Isr, IsrArc, Local};
+pub use barrier::Barrier;
 pub use mutex::Mutex;
+pub use notify::Notify;
+pub use pipe::Pipe;
+pub use priority_queue::PriorityQueue;
 pub use queue::Queue;
 pub use semaphore::Semaphore;
@@ -14,12 +14,12 @@ use super::semaphore::{Acquire, Semaphore};
 
 pub struct TryLockError;
 
-pub struct Mutex<T, const N: usize> {
-    lock: Semaphore<N>,
+pub struct Mutex<T> {
+    lock: Semaphore,
     value: UnsafeCell<T>,
 }
 
-impl<T, const N: usize> Mutex<T, N> {
+impl<T> Mutex<T> {
     #[inline(always)]
     pub const fn new(initial: T) -> Self {
         Self {
@@ -29,17 +29,15 @@ impl<T, const N: usize> Mutex<T, N> {
     }
 
     #[inline(always)]
-    pub fn lock(&mut self) -> Lock<T, N> {
+    pub fn lock(&self) -> Lock<T> {
         Lock::new(self)
     }
 
-    pub fn try_lock(&mut self) -> Result<MutexGuard<T, N>, TryLockError> {
-        unsafe { (*(self as *mut Self)).lock.try_acquire() }
-            .map_err(|_| TryLockError)
-            .map(|x| {
-                core::mem::forget(x);
-                MutexGuard { mutex: self }
-            })
+    pub fn try_lock(&self) -> Result<MutexGuard<T>, TryLockError> {
+        self.lock.try_acquire().map_err(|_| TryLockError).map(|x| {
+            core::mem::forget(x);
+            MutexGuard { mutex: self }
+        })
     }
 
     #[doc(hidden)]
@@ -49,24 +47,24 @@ impl<T, const N: usize> Mutex<T, N> {
     }
 }
 
-impl<T, const N: usize> Ready for Mutex<T, N> {
+impl<T> Ready for Mutex<T> {
     #[inline]
     fn is_ready(&self, cs: &avr_device::interrupt::CriticalSection) -> bool {
         self.lock.is_ready(cs)
     }
 }
 
-unsafe impl<T: Send, const N: usize> Send for Mutex<T, N> {}
-unsafe impl<T: Send, const N: usize> Sync for Mutex<T, N> {}
+unsafe impl<T: Send> Send for Mutex<T> {}
+unsafe impl<T: Send> Sync for Mutex<T> {}
 
-pub struct MutexGuard<'a, T, const N: usize> {
-    mutex: &'a mut Mutex<T, N>,
+pub struct MutexGuard<'a, T> {
+    mutex: &'a Mutex<T>,
 }
 
-impl<T, const N: usize> !Send for MutexGuard<'_, T, N> {}
-unsafe impl<T: Sync, const N: usize> Sync for MutexGuard<'_, T, N> {}
+impl<T> !Send for MutexGuard<'_, T> {}
+unsafe impl<T: Sync> Sync for MutexGuard<'_, T> {}
 
-impl<T, const N: usize> Deref for MutexGuard<'_, T, N> {
+impl<T> Deref for MutexGuard<'_, T> {
     type Target = T;
 
     #[inline(always)]
@@ -75,46 +73,46 @@ impl<T, const N: usize> Deref for MutexGuard<'_, T, N> {
     }
 }
 
-impl<T, const N: usize> DerefMut for MutexGuard<'_, T, N> {
+impl<T> DerefMut for MutexGuard<'_, T> {
     #[inline(always)]
     fn deref_mut(&mut self) -> &mut Self::Target {
         unsafe { &mut *self.mutex.value.get() }
     }
 }
 
-impl<T, const N: usize> Drop for MutexGuard<'_, T, N> {
+impl<T> Drop for MutexGuard<'_, T> {
     #[inline]
     fn drop(&mut self) {
         self.mutex.lock.inner().release(1);
     }
 }
 
-impl<'a, T, const N: usize> MutexGuard<'a, T, N> {
-    pub fn map<U, F>(mut this: Self, f: F) -> MappedMutexGuard<'a, U, N>
+impl<'a, T> MutexGuard<'a, T> {
+    pub fn map<U, F>(mut this: Self, f: F) -> MappedMutexGuard<'a, U>
     where
         F: FnOnce(&mut T) -> &mut U,
     {
         let data = f(&mut *this) as *mut U;
-        let s = &mut this.mutex.lock as *mut Semaphore<N>;
+        let s = &this.mutex.lock as *const Semaphore;
         core::mem::forget(this);
         MappedMutexGuard {
-            s: unsafe { &mut *s },
+            s: unsafe { &*s },
             data,
             marker: core::marker::PhantomData,
         }
     }
 }
 
-pub struct MappedMutexGuard<'a, T, const N: usize> {
-    s: &'a mut Semaphore<N>,
+pub struct MappedMutexGuard<'a, T> {
+    s: &'a Semaphore,
     data: *mut T,
     marker: core::marker::PhantomData<&'a mut T>,
 }
 
-unsafe impl<'a, T, const N: usize> Sync for MappedMutexGuard<'a, T, N> where T: Sync + 'a {}
-unsafe impl<'a, T, const N: usize> Send for MappedMutexGuard<'a, T, N> where T: Send + 'a {}
+unsafe impl<'a, T> Sync for MappedMutexGuard<'a, T> where T: Sync + 'a {}
+unsafe impl<'a, T> Send for MappedMutexGuard<'a, T> where T: Send + 'a {}
 
-impl<'a, T, const N: usize> Deref for MappedMutexGuard<'a, T, N> {
+impl<'a, T> Deref for MappedMutexGuard<'a, T> {
     type Target = T;
 
     #[inline(always)]
@@ -123,67 +121,62 @@ impl<'a, T, const N: usize> Deref for MappedMutexGuard<'a, T, N> {
     }
 }
 
-impl<'a, T, const N: usize> DerefMut for MappedMutexGuard<'a, T, N> {
+impl<'a, T> DerefMut for MappedMutexGuard<'a, T> {
     #[inline(always)]
     fn deref_mut(&mut self) -> &mut Self::Target {
         unsafe { &mut *self.data }
     }
 }
 
-impl<'a, T, const N: usize> Drop for MappedMutexGuard<'a, T, N> {
+impl<'a, T> Drop for MappedMutexGuard<'a, T> {
     #[inline]
     fn drop(&mut self) {
         self.s.inner().release(1);
     }
 }
 
-#[allow(clippy::type_complexity)]
-pub struct Lock<'a, T, const N: usize> {
-    state: Option<(&'a mut Mutex<T, N>, Acquire<'a, N>)>,
+pub struct Lock<'a, T> {
+    mutex: Option<&'a Mutex<T>>,
+    acquire: Acquire<'a>,
 }
 
-impl<'a, T, const N: usize> Lock<'a, T, N> {
+impl<'a, T> Lock<'a, T> {
     #[inline]
-    pub fn new(mutex: &'a mut Mutex<T, N>) -> Self {
-        let acquire = unsafe { (*(mutex as *mut Mutex<T, N>)).lock.acquire() };
+    pub fn new(mutex: &'a Mutex<T>) -> Self {
+        let acquire = mutex.lock.acquire();
         Self {
-            state: Some((mutex, acquire)),
+            mutex: Some(mutex),
+            acquire,
         }
     }
+}
 
-    fn poll(&mut self, cx: &mut core::task::Context<'_>) -> Poll<MutexGuard<'a, T, N>> {
-        let (mutex, mut acquire) = unsafe { self.state.take().unwrap_unchecked() };
+impl<'a, T> Future for Lock<'a, T> {
+    type Output = MutexGuard<'a, T>;
 
-        match acquire.poll(cx) {
-            Poll::Pending => {
-                self.state = Some((mutex, acquire));
-                Poll::Pending
-            }
+    fn poll(self: Pin<&mut Self>, cx: &mut core::task::Context<'_>) -> Poll<Self::Output> {
+        let this = unsafe { Pin::get_unchecked_mut(self) };
+
+        match unsafe { Pin::new_unchecked(&mut this.acquire) }.poll(cx) {
+            Poll::Pending => Poll::Pending,
             Poll::Ready(perm) => {
                 core::mem::forget(perm);
-                Poll::Ready(MutexGuard { mutex })
+                Poll::Ready(MutexGuard {
+                    mutex: unsafe { this.mutex.take().unwrap_unchecked() },
+                })
             }
         }
     }
 }
 
-impl<'a, T, const N: usize> Future for Lock<'a, T, N> {
-    type Output = MutexGuard<'a, T, N>;
-
-    #[inline(always)]
-    fn poll(self: Pin<&mut Self>, cx: &mut core::task::Context<'_>) -> Poll<Self::Output> {
-        unsafe { Pin::get_unchecked_mut(self) }.poll(cx)
-    }
-}
-
-impl<T: Default, const N: usize> Default for Mutex<T, N> {
+impl<T: Default> Default for Mutex<T> {
     #[inline]
     fn default() -> Self {
         Self::new(Default::default())
     }
 }
 
-impl<T: Default, const N: usize> From<T> for Mutex<T, N> {
+impl<T: Default> From<T> for Mutex<T> {
     #[inline]
     fn from(t: T) -> Self {
         Self::new(t)
@@ -0,0 +1,173 @@
+use core::{cell::UnsafeCell, future::Future, marker::PhantomData, pin::Pin, task::Poll};
+
+use avr_device::interrupt::CriticalSection;
+
+use crate::{
+    runtime::Ready,
+    sync::waitlist::{Node, WaitList},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Notification {
+    None,
+    One,
+    All,
+}
+
+#[derive(Debug)]
+struct InnerNotify {
+    waiters: WaitList<Notification>,
+    permit: bool,
+    pending: usize,
+}
+
+impl InnerNotify {
+    fn notify_one(&mut self) {
+        if let Some(node) = self.waiters.pop_front() {
+            unsafe { (*node).value = Notification::One };
+            self.pending += 1;
+            unsafe { crate::executor::wake() };
+        } else {
+            self.permit = true;
+        }
+    }
+
+    fn notify_all(&mut self) {
+        let mut signal = false;
+
+        while let Some(node) = self.waiters.pop_front() {
+            unsafe { (*node).value = Notification::All };
+            self.pending += 1;
+            signal = true;
+        }
+
+        if signal {
+            unsafe { crate::executor::wake() };
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Notify {
+    inner: UnsafeCell<InnerNotify>,
+}
+
+impl Notify {
+    #[inline(always)]
+    pub const fn new() -> Self {
+        Self {
+            inner: UnsafeCell::new(InnerNotify {
+                waiters: WaitList::new(),
+                permit: false,
+                pending: 0,
+            }),
+        }
+    }
+
+    /// Wakes the oldest waiter. If nobody is waiting the notification is stored and the next call
+    /// to [`Notify::notified`] completes immediately.
+    #[inline(always)]
+    pub fn notify_one(&self) {
+        self.inner().notify_one()
+    }
+
+    /// Wakes every task currently waiting. Nothing is stored for later waiters.
+    #[inline(always)]
+    pub fn notify_all(&self) {
+        self.inner().notify_all()
+    }
+
+    #[inline(always)]
+    pub fn notified(&self) -> Notified {
+        Notified {
+            notify: self.inner.get(),
+            state: State::Idle,
+            node: Node::new(Notification::None),
+            _life: PhantomData,
+        }
+    }
+
+    #[allow(clippy::mut_from_ref)]
+    #[inline(always)]
+    fn inner(&self) -> &mut InnerNotify {
+        unsafe { &mut *(self.inner.get()) }
+    }
+}
+
+impl Ready for Notify {
+    #[inline]
+    fn is_ready(&self, _: &CriticalSection) -> bool {
+        self.inner().pending != 0
+    }
+}
+
+impl Default for Notify {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+enum State {
+    Idle,
+    Waiting,
+    Done,
+}
+
+pub struct Notified<'a> {
+    notify: *mut InnerNotify,
+    state: State,
+    node: Node<Notification>,
+    _life: PhantomData<&'a Notify>,
+}
+
+impl<'a> Future for Notified<'a> {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, _cx: &mut core::task::Context<'_>) -> Poll<Self::Output> {
+        let this = unsafe { Pin::get_unchecked_mut(self) };
+        let notify = unsafe { &mut *this.notify };
+
+        match this.state {
+            State::Idle => {
+                if notify.permit {
+                    notify.permit = false;
+                    this.state = State::Done;
+                    Poll::Ready(())
+                } else {
+                    unsafe { notify.waiters.push_back(&mut this.node) };
+                    this.state = State::Waiting;
+                    Poll::Pending
+                }
+            }
+            State::Waiting => {
+                if this.node.value == Notification::None {
+                    Poll::Pending
+                } else {
+                    notify.pending -= 1;
+                    this.state = State::Done;
+                    Poll::Ready(())
+                }
+            }
+            State::Done => panic!("Notified polled after completion"),
+        }
+    }
+}
+
+impl<'a> Drop for Notified<'a> {
+    fn drop(&mut self) {
+        if let State::Waiting = self.state {
+            let notify = unsafe { &mut *self.notify };
+
+            if self.node.is_linked() {
+                unsafe { notify.waiters.remove(&mut self.node) };
+            } else {
+                notify.pending -= 1;
+                // A single notification must not get lost with the waiter that received it.
+                if self.node.value == Notification::One {
+                    notify.notify_one();
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,281 @@
+use core::{future::Future, marker::PhantomData, pin::Pin, task::Poll};
+
+use avr_device::interrupt::CriticalSection;
+
+use crate::runtime::Ready;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    /// The reading half has been dropped, written bytes would never be consumed.
+    Closed,
+}
+
+impl embedded_io::Error for Error {
+    #[inline]
+    fn kind(&self) -> embedded_io::ErrorKind {
+        match self {
+            Error::Closed => embedded_io::ErrorKind::BrokenPipe,
+        }
+    }
+}
+
+pub struct Pipe<const N: usize> {
+    buffer: crate::queue::Queue<u8, N>,
+    reader_closed: bool,
+    writer_closed: bool,
+}
+
+impl<const N: usize> Pipe<N> {
+    #[inline(always)]
+    pub const fn new() -> Self {
+        Self {
+            buffer: crate::queue::Queue::new(),
+            reader_closed: false,
+            writer_closed: false,
+        }
+    }
+
+    #[inline(always)]
+    pub fn split(&mut self) -> (Reader<N>, Writer<N>) {
+        self.reader_closed = false;
+        self.writer_closed = false;
+
+        (
+            Reader {
+                pipe: self as *mut _,
+                _life: PhantomData,
+            },
+            Writer {
+                pipe: self as *mut _,
+                _life: PhantomData,
+            },
+        )
+    }
+
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.buffer.len()
+    }
+
+    #[inline(always)]
+    pub const fn is_empty(&self) -> bool {
+        self.buffer.is_empty()
+    }
+
+    #[inline(always)]
+    pub fn is_full(&self) -> bool {
+        self.buffer.is_full()
+    }
+
+    fn try_read(&mut self, buf: &mut [u8]) -> usize {
+        let full = self.buffer.is_full();
+        let mut len = 0;
+
+        for b in buf.iter_mut() {
+            match self.buffer.dequeue() {
+                Some(x) => *b = x,
+                None => break,
+            }
+            len += 1;
+        }
+
+        // A writer waits for room, a flush for an empty buffer.
+        if len != 0 && (full || self.buffer.is_empty()) {
+            unsafe { crate::executor::wake() };
+        }
+
+        len
+    }
+
+    fn try_write(&mut self, buf: &[u8]) -> usize {
+        let signal = self.buffer.is_empty();
+        let mut len = 0;
+
+        for &b in buf {
+            if self.buffer.enqueue(b).is_err() {
+                break;
+            }
+            len += 1;
+        }
+
+        if signal && len != 0 {
+            unsafe { crate::executor::wake() };
+        }
+
+        len
+    }
+}
+
+impl<const N: usize> Ready for Pipe<N> {
+    #[inline]
+    fn is_ready(&self, _: &CriticalSection) -> bool {
+        !self.buffer.is_empty()
+    }
+}
+
+impl<const N: usize> Default for Pipe<N> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub struct Reader<'a, const N: usize> {
+    pipe: *mut Pipe<N>,
+    _life: PhantomData<&'a mut Pipe<N>>,
+}
+
+impl<'a, const N: usize> Reader<'a, N> {
+    /// Waits until at least one byte is available and reads as many as fit in `buf`.
+    ///
+    /// Resolves to 0 only if `buf` is empty or the writer has been dropped and the pipe is
+    /// drained.
+    #[inline(always)]
+    pub fn read<'b>(&'b mut self, buf: &'b mut [u8]) -> Read<'b, N> {
+        Read {
+            pipe: self.pipe,
+            buf,
+            _life: PhantomData,
+        }
+    }
+}
+
+impl<'a, const N: usize> Drop for Reader<'a, N> {
+    #[inline]
+    fn drop(&mut self) {
+        unsafe { (*self.pipe).reader_closed = true };
+        unsafe { crate::executor::wake() };
+    }
+}
+
+// The halves and their futures share the pipe through the raw pointer, a reference only lives for
+// the duration of a call.
+pub struct Read<'a, const N: usize> {
+    pipe: *mut Pipe<N>,
+    buf: &'a mut [u8],
+    _life: PhantomData<&'a mut Pipe<N>>,
+}
+
+impl<'a, const N: usize> Future for Read<'a, N> {
+    type Output = usize;
+
+    fn poll(self: Pin<&mut Self>, _cx: &mut core::task::Context<'_>) -> Poll<Self::Output> {
+        let this = Pin::get_mut(self);
+
+        if this.buf.is_empty() {
+            return Poll::Ready(0);
+        }
+
+        match unsafe { (*this.pipe).try_read(this.buf) } {
+            0 if unsafe { !(*this.pipe).writer_closed } => Poll::Pending,
+            len => Poll::Ready(len),
+        }
+    }
+}
+
+pub struct Writer<'a, const N: usize> {
+    pipe: *mut Pipe<N>,
+    _life: PhantomData<&'a mut Pipe<N>>,
+}
+
+impl<'a, const N: usize> Writer<'a, N> {
+    /// Waits until there is room for at least one byte and writes as many as fit from `buf`.
+    #[inline(always)]
+    pub fn write<'b>(&'b mut self, buf: &'b [u8]) -> Write<'b, N> {
+        Write {
+            pipe: self.pipe,
+            buf,
+            _life: PhantomData,
+        }
+    }
+
+    /// Waits until the reader has consumed every byte written so far.
+    #[inline(always)]
+    pub fn flush(&mut self) -> Flush<N> {
+        Flush {
+            pipe: self.pipe,
+            _life: PhantomData,
+        }
+    }
+}
+
+impl<'a, const N: usize> Drop for Writer<'a, N> {
+    #[inline]
+    fn drop(&mut self) {
+        unsafe { (*self.pipe).writer_closed = true };
+        unsafe { crate::executor::wake() };
+    }
+}
+
+pub struct Write<'a, const N: usize> {
+    pipe: *mut Pipe<N>,
+    buf: &'a [u8],
+    _life: PhantomData<&'a mut Pipe<N>>,
+}
+
+impl<'a, const N: usize> Future for Write<'a, N> {
+    type Output = Result<usize, Error>;
+
+    fn poll(self: Pin<&mut Self>, _cx: &mut core::task::Context<'_>) -> Poll<Self::Output> {
+        let this = Pin::get_mut(self);
+
+        if this.buf.is_empty() {
+            Poll::Ready(Ok(0))
+        } else if unsafe { (*this.pipe).reader_closed } {
+            Poll::Ready(Err(Error::Closed))
+        } else {
+            match unsafe { (*this.pipe).try_write(this.buf) } {
+                0 => Poll::Pending,
+                len => Poll::Ready(Ok(len)),
+            }
+        }
+    }
+}
+
+pub struct Flush<'a, const N: usize> {
+    pipe: *mut Pipe<N>,
+    _life: PhantomData<&'a mut Pipe<N>>,
+}
+
+impl<'a, const N: usize> Future for Flush<'a, N> {
+    type Output = Result<(), Error>;
+
+    fn poll(self: Pin<&mut Self>, _cx: &mut core::task::Context<'_>) -> Poll<Self::Output> {
+        let pipe = unsafe { &*self.pipe };
+
+        if pipe.buffer.is_empty() {
+            Poll::Ready(Ok(()))
+        } else if pipe.reader_closed {
+            Poll::Ready(Err(Error::Closed))
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+impl<'a, const N: usize> embedded_io::ErrorType for Reader<'a, N> {
+    type Error = core::convert::Infallible;
+}
+
+impl<'a, const N: usize> embedded_io_async::Read for Reader<'a, N> {
+    #[inline]
+    async fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        Ok(Reader::read(self, buf).await)
+    }
+}
+
+impl<'a, const N: usize> embedded_io::ErrorType for Writer<'a, N> {
+    type Error = Error;
+}
+
+impl<'a, const N: usize> embedded_io_async::Write for Writer<'a, N> {
+    #[inline]
+    async fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+        Writer::write(self, buf).await
+    }
+
+    #[inline]
+    async fn flush(&mut self) -> Result<(), Self::Error> {
+        Writer::flush(self).await
+    }
+}
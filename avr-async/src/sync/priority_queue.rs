@@ -0,0 +1,134 @@
+use core::{future::Future, pin::Pin, task::Poll};
+
+use crate::{queue::BinaryHeap, runtime::Ready};
+
+/// Like [`crate::sync::Queue`], but values come out greatest first.
+pub struct PriorityQueue<T: Ord, const N: usize> {
+    inner: BinaryHeap<T, N>,
+}
+
+impl<T: Ord, const N: usize> PriorityQueue<T, N> {
+    #[inline(always)]
+    pub const fn new() -> Self {
+        Self {
+            inner: BinaryHeap::new(),
+        }
+    }
+
+    #[inline(always)]
+    pub const fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    #[inline(always)]
+    pub const fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    #[inline(always)]
+    pub fn peek(&self) -> Option<&T> {
+        self.inner.peek()
+    }
+
+    #[inline(always)]
+    pub fn try_push(&mut self, val: T) -> Result<(), T> {
+        let signal = self.inner.is_empty();
+        self.inner.push(val).map(|x| {
+            if signal {
+                unsafe { crate::executor::wake() };
+            }
+            x
+        })
+    }
+
+    #[inline(always)]
+    pub fn try_pop(&mut self) -> Option<T> {
+        let signal = self.inner.is_full();
+        self.inner.pop().map(|x| {
+            if signal {
+                unsafe { crate::executor::wake() };
+            }
+            x
+        })
+    }
+
+    #[inline(always)]
+    pub fn push(&mut self, val: T) -> Push<T, N> {
+        Push::new(self, val)
+    }
+
+    #[inline(always)]
+    pub fn pop(&mut self) -> Pop<T, N> {
+        Pop::new(self)
+    }
+}
+
+impl<T: Ord, const N: usize> Ready for PriorityQueue<T, N> {
+    #[inline]
+    fn is_ready(&self, _: &avr_device::interrupt::CriticalSection) -> bool {
+        !self.inner.is_empty()
+    }
+}
+
+impl<T: Ord, const N: usize> Default for PriorityQueue<T, N> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub struct Push<'a, T: Ord, const N: usize> {
+    q: &'a mut PriorityQueue<T, N>,
+    v: Option<T>,
+}
+
+impl<'a, T: Ord, const N: usize> Push<'a, T, N> {
+    #[inline(always)]
+    pub fn new(q: &'a mut PriorityQueue<T, N>, val: T) -> Self {
+        Self { q, v: Some(val) }
+    }
+}
+
+impl<'a, T: Ord, const N: usize> Future for Push<'a, T, N> {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, _cx: &mut core::task::Context<'_>) -> Poll<Self::Output> {
+        let this = unsafe { Pin::get_unchecked_mut(self) };
+
+        match this.q.try_push(this.v.take().unwrap()) {
+            Ok(()) => Poll::Ready(()),
+            Err(v) => {
+                this.v.replace(v);
+                Poll::Pending
+            }
+        }
+    }
+}
+
+pub struct Pop<'a, T: Ord, const N: usize> {
+    q: Option<&'a mut PriorityQueue<T, N>>,
+}
+
+impl<'a, T: Ord, const N: usize> Pop<'a, T, N> {
+    #[inline(always)]
+    pub fn new(q: &'a mut PriorityQueue<T, N>) -> Self {
+        Self { q: Some(q) }
+    }
+}
+
+impl<'a, T: Ord, const N: usize> Future for Pop<'a, T, N> {
+    type Output = T;
+
+    fn poll(self: Pin<&mut Self>, _cx: &mut core::task::Context<'_>) -> Poll<Self::Output> {
+        let this = unsafe { Pin::get_unchecked_mut(self) };
+        let q = this.q.take().unwrap();
+
+        match q.try_pop() {
+            Some(val) => Poll::Ready(val),
+            None => {
+                this.q.replace(q);
+                Poll::Pending
+            }
+        }
+    }
+}
@@ -1,31 +1,26 @@
-use core::{cell::UnsafeCell, mem::MaybeUninit};
-
-use either::Either;
-
-use crate::runtime::Ready;
+use crate::{
+    runtime::Ready,
+    sync::waitlist::{Node, WaitList},
+};
 
 pub struct TryAcquireError;
 
+/// Waiters are stored in the `Acquire` futures themselves, each node holds the number of permits
+/// it still needs. Permits are reserved in FIFO order as they become available.
 #[derive(Debug)]
-pub struct InnerSemaphore<const N: usize> {
+pub struct InnerSemaphore {
     permits: usize,
     locking: usize,
-    bounds: Option<(usize, usize)>,
-    #[allow(clippy::type_complexity)]
-    buffer: [UnsafeCell<MaybeUninit<Option<usize>>>; N],
+    waiters: WaitList<usize>,
 }
 
-impl<const N: usize> InnerSemaphore<N> {
-    #[allow(clippy::declare_interior_mutable_const)]
-    const INIT: UnsafeCell<MaybeUninit<Option<usize>>> = UnsafeCell::new(MaybeUninit::uninit());
-
+impl InnerSemaphore {
     #[inline(always)]
     pub const fn new(permits: usize) -> Self {
         Self {
             permits,
             locking: 0,
-            bounds: None,
-            buffer: [Self::INIT; N],
+            waiters: WaitList::new(),
         }
     }
 
@@ -37,35 +32,15 @@ impl<const N: usize> InnerSemaphore<N> {
 
     #[inline]
     pub fn len(&self) -> usize {
-        self.bounds
-            .as_ref()
-            .map(|&(head, tail)| (tail.wrapping_sub(head).wrapping_add(N) % N) + 1)
-            .unwrap_or(0)
+        self.waiters.len()
     }
 
     #[inline(always)]
-    pub const fn is_empty(&self) -> bool {
-        matches!(self.bounds, None)
+    pub fn is_empty(&self) -> bool {
+        self.waiters.is_empty()
     }
 
-    #[inline]
-    pub fn is_full(&self) -> bool {
-        self.bounds
-            .as_ref()
-            .map(|&(h, t)| h == Self::inc(t))
-            .unwrap_or(false)
-    }
-
-    pub fn try_acquire_enqueue(&mut self, perms: usize) -> Option<TryAcquireEnqueue<N>> {
-        if let Ok(p) = unsafe { (*(self as *mut Self)).try_acquire(perms) } {
-            return Some(TryAcquireEnqueue::from_permit(p));
-        } else {
-            self.try_enqueue(perms)
-                .map(TryAcquireEnqueue::from_enqued_lock)
-        }
-    }
-
-    pub fn try_acquire(&mut self, perms: usize) -> Result<SemaphorePermit<N>, TryAcquireError> {
+    pub fn try_acquire(&mut self, perms: usize) -> Result<SemaphorePermit, TryAcquireError> {
         if perms > self.permits {
             panic!("Too many permits requested");
         }
@@ -79,69 +54,17 @@ impl<const N: usize> InnerSemaphore<N> {
         }
     }
 
-    #[inline]
-    fn try_enqueue(&mut self, perms: usize) -> Option<EnqueuedLock<N>> {
-        unsafe { self.inner_enqueue(perms) }
-    }
-
-    unsafe fn inner_enqueue(&mut self, perms: usize) -> Option<EnqueuedLock<N>> {
-        match match self.bounds {
-            Some((head, tail)) => {
-                let next_tail = Self::inc(tail);
-
-                if head == next_tail {
-                    None
-                } else {
-                    Some((head, next_tail))
-                }
-            }
-            None => Some((0, 0)),
-        } {
-            Some((head, tail)) => {
-                self.bounds = Some((head, tail));
-                let ptr = self.buffer.get_unchecked(tail).get();
-                ptr.write(MaybeUninit::new(Some(perms)));
-                Some(EnqueuedLock::new(self, ptr as *mut Option<usize>, perms))
-            }
-            None => None,
-        }
-    }
-
-    #[inline(always)]
-    const fn inc(val: usize) -> usize {
-        (val + 1) % N
+    /// # Safety
+    /// `node` must be pinned and must be dequeued before it is dropped.
+    pub(crate) unsafe fn enqueue(&mut self, node: *mut Node<usize>) {
+        self.waiters.push_back(node);
+        self.progress_queue();
     }
 
-    fn dequeued_descriptor(&mut self) {
-        let was_full = self.is_full();
-        let mut signal = false;
-
-        loop {
-            unsafe {
-                match self.bounds {
-                    Some((head, tail)) => {
-                        if (*(self.buffer.get_unchecked(head).get() as *const Option<usize>))
-                            .is_none()
-                        {
-                            let new_len = tail.wrapping_sub(head).wrapping_add(N) % N;
-                            if new_len == 0 {
-                                self.bounds = None;
-                            } else {
-                                self.bounds = Some((Self::inc(head), tail));
-                            }
-                            signal = true;
-                        } else {
-                            break;
-                        }
-                    }
-                    None => break,
-                }
-            }
-        }
-
-        if was_full && signal {
-            unsafe { crate::executor::wake() };
-        }
+    /// # Safety
+    /// `node` must be enqueued in this semaphore.
+    pub(crate) unsafe fn dequeue(&mut self, node: *mut Node<usize>) {
+        self.waiters.remove(node);
     }
 
     pub(crate) fn release(&mut self, permits: usize) {
@@ -150,29 +73,23 @@ impl<const N: usize> InnerSemaphore<N> {
     }
 
     fn progress_queue(&mut self) {
-        let len = match self.len() {
-            0 => return,
-            x => x,
-        };
-        let head = unsafe { self.bounds.unwrap_unchecked() }.0;
         let mut signal = false;
 
-        for i in 0..len {
-            let idx = head.wrapping_add(i) % N;
-            if let Some(task) =
-                unsafe { &mut *(self.buffer.get_unchecked(idx).get() as *mut Option<usize>) }
-                    .as_mut()
-            {
-                let remaining = self.permits - self.locking;
-                if remaining <= *task {
-                    *task -= remaining;
-                    self.locking = self.permits;
-                    return;
-                } else {
-                    self.locking += *task;
-                    *task = 0;
-                    signal = true;
-                }
+        for node in self.waiters.iter() {
+            let task = unsafe { &mut (*node).value };
+            if *task == 0 {
+                continue;
+            }
+
+            let remaining = self.permits - self.locking;
+            if remaining < *task {
+                *task -= remaining;
+                self.locking = self.permits;
+                break;
+            } else {
+                self.locking += *task;
+                *task = 0;
+                signal = true;
             }
         }
 
@@ -182,7 +99,7 @@ impl<const N: usize> InnerSemaphore<N> {
     }
 }
 
-impl<const N: usize> Ready for InnerSemaphore<N> {
+impl Ready for InnerSemaphore {
     #[inline]
     fn is_ready(&self, _: &avr_device::interrupt::CriticalSection) -> bool {
         !self.is_empty()
@@ -190,61 +107,14 @@ impl<const N: usize> Ready for InnerSemaphore<N> {
 }
 
 #[derive(Debug)]
-struct EnqueuedLock<'a, const N: usize> {
-    q: &'a mut InnerSemaphore<N>,
-    descriptor: &'a mut Option<usize>,
+pub struct SemaphorePermit<'a> {
+    q: &'a mut InnerSemaphore,
     permits: usize,
 }
 
-impl<'a, const N: usize> EnqueuedLock<'a, N> {
+impl<'a> SemaphorePermit<'a> {
     #[inline(always)]
-    unsafe fn new(
-        q: &'a mut InnerSemaphore<N>,
-        descriptor: *mut Option<usize>,
-        permits: usize,
-    ) -> Self {
-        let descriptor = &mut *descriptor;
-
-        Self {
-            q,
-            descriptor,
-            permits,
-        }
-    }
-
-    pub fn try_lock(self) -> Either<SemaphorePermit<'a, N>, Self> {
-        if unsafe { self.descriptor.unwrap_unchecked() } == 0 {
-            *self.descriptor = None;
-            self.q.dequeued_descriptor();
-            Either::Left(unsafe {
-                SemaphorePermit::new(&mut *(self.q as *mut InnerSemaphore<N>), self.permits)
-            })
-        } else {
-            Either::Right(self)
-        }
-    }
-}
-
-impl<'a, const N: usize> Drop for EnqueuedLock<'a, N> {
-    fn drop(&mut self) {
-        if let Some(d) = self.descriptor {
-            let permits = self.permits - *d;
-            *self.descriptor = None;
-            self.q.dequeued_descriptor();
-            self.q.release(permits);
-        }
-    }
-}
-
-#[derive(Debug)]
-pub struct SemaphorePermit<'a, const N: usize> {
-    q: &'a mut InnerSemaphore<N>,
-    permits: usize,
-}
-
-impl<'a, const N: usize> SemaphorePermit<'a, N> {
-    #[inline(always)]
-    unsafe fn new(q: &'a mut InnerSemaphore<N>, total: usize) -> Self {
+    pub(crate) unsafe fn new(q: &'a mut InnerSemaphore, total: usize) -> Self {
         Self { q, permits: total }
     }
 
@@ -254,39 +124,9 @@ impl<'a, const N: usize> SemaphorePermit<'a, N> {
     }
 }
 
-impl<'a, const N: usize> Drop for SemaphorePermit<'a, N> {
+impl<'a> Drop for SemaphorePermit<'a> {
     #[inline(always)]
     fn drop(&mut self) {
         self.q.release(self.permits);
     }
 }
-
-#[derive(Debug)]
-pub struct TryAcquireEnqueue<'a, const N: usize> {
-    state: Either<SemaphorePermit<'a, N>, EnqueuedLock<'a, N>>,
-}
-
-impl<'a, const N: usize> TryAcquireEnqueue<'a, N> {
-    #[inline(always)]
-    const fn from_permit(p: SemaphorePermit<'a, N>) -> Self {
-        Self {
-            state: Either::Left(p),
-        }
-    }
-    #[inline(always)]
-    const fn from_enqued_lock(p: EnqueuedLock<'a, N>) -> Self {
-        Self {
-            state: Either::Right(p),
-        }
-    }
-
-    pub fn try_lock(self) -> Either<SemaphorePermit<'a, N>, Self> {
-        match self.state {
-            Either::Left(p) => Either::Left(p),
-            Either::Right(q) => match q.try_lock() {
-                Either::Left(p) => Either::Left(p),
-                Either::Right(q) => Either::Right(Self::from_enqued_lock(q)),
-            },
-        }
-    }
-}
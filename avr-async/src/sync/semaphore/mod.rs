@@ -1,19 +1,17 @@
 mod imp;
 
-use core::{cell::UnsafeCell, future::Future, pin::Pin, task::Poll};
+use core::{cell::UnsafeCell, future::Future, marker::PhantomData, pin::Pin, task::Poll};
 
-use either::Either;
-
-use crate::runtime::Ready;
+use crate::{runtime::Ready, sync::waitlist::Node};
 
 pub use self::imp::{SemaphorePermit, TryAcquireError};
 
 #[derive(Debug)]
-pub struct Semaphore<const N: usize> {
-    pub(crate) inner: UnsafeCell<imp::InnerSemaphore<N>>,
+pub struct Semaphore {
+    pub(crate) inner: UnsafeCell<imp::InnerSemaphore>,
 }
 
-impl<const N: usize> Semaphore<N> {
+impl Semaphore {
     #[inline(always)]
     pub const fn new(permits: usize) -> Self {
         Self {
@@ -27,81 +25,100 @@ impl<const N: usize> Semaphore<N> {
     }
 
     #[inline(always)]
-    pub fn try_acquire_many(&self, n: usize) -> Result<SemaphorePermit<N>, TryAcquireError> {
+    pub fn try_acquire_many(&self, n: usize) -> Result<SemaphorePermit, TryAcquireError> {
         self.inner().try_acquire(n)
     }
 
     #[inline(always)]
-    pub fn try_acquire(&self) -> Result<SemaphorePermit<N>, TryAcquireError> {
+    pub fn try_acquire(&self) -> Result<SemaphorePermit, TryAcquireError> {
         self.try_acquire_many(1)
     }
 
-    pub fn acquire_many(&self, n: usize) -> Acquire<N> {
+    pub fn acquire_many(&self, n: usize) -> Acquire {
         Acquire::new(self.inner.get(), n)
     }
 
     #[inline(always)]
-    pub fn acquire(&self) -> Acquire<N> {
+    pub fn acquire(&self) -> Acquire {
         self.acquire_many(1)
     }
 
     #[allow(clippy::mut_from_ref)]
-    pub(crate) fn inner(&self) -> &mut imp::InnerSemaphore<N> {
+    pub(crate) fn inner(&self) -> &mut imp::InnerSemaphore {
         unsafe { &mut *(self.inner.get()) }
     }
 }
 
-impl<const N: usize> Ready for Semaphore<N> {
+impl Ready for Semaphore {
     #[inline]
     fn is_ready(&self, cs: &avr_device::interrupt::CriticalSection) -> bool {
         self.inner().is_ready(cs)
     }
 }
 
-pub struct Acquire<'a, const N: usize> {
-    state: Option<Either<(&'a mut imp::InnerSemaphore<N>, usize), imp::TryAcquireEnqueue<'a, N>>>,
+enum State {
+    Idle,
+    Waiting,
+    Done,
+}
+
+pub struct Acquire<'a> {
+    q: *mut imp::InnerSemaphore,
+    permits: usize,
+    state: State,
+    node: Node<usize>,
+    _life: PhantomData<&'a mut imp::InnerSemaphore>,
 }
 
-impl<'a, const N: usize> Acquire<'a, N> {
+impl<'a> Acquire<'a> {
     #[inline(always)]
-    fn new(q: *mut imp::InnerSemaphore<N>, n: usize) -> Self {
+    fn new(q: *mut imp::InnerSemaphore, n: usize) -> Self {
         Self {
-            state: Some(Either::Left((unsafe { &mut *q }, n))),
+            q,
+            permits: n,
+            state: State::Idle,
+            node: Node::new(n),
+            _life: PhantomData,
         }
     }
+}
 
-    pub(crate) fn poll(
-        &mut self,
-        _cx: &mut core::task::Context<'_>,
-    ) -> Poll<SemaphorePermit<'a, N>> {
-        self.state = Some(loop {
-            match unsafe { self.state.take().unwrap_unchecked() } {
-                Either::Left((q, n)) => {
-                    if let Some(p) =
-                        unsafe { (*(q as *mut imp::InnerSemaphore<N>)).try_acquire_enqueue(n) }
-                    {
-                        self.state = Some(Either::Right(p));
-                    } else {
-                        break Either::Left((q, n));
-                    }
-                }
-                Either::Right(p) => match p.try_lock() {
-                    Either::Left(s) => return Poll::Ready(s),
-                    Either::Right(p) => break Either::Right(p),
-                },
+impl<'a> Future for Acquire<'a> {
+    type Output = SemaphorePermit<'a>;
+
+    fn poll(self: Pin<&mut Self>, _cx: &mut core::task::Context<'_>) -> Poll<Self::Output> {
+        let this = unsafe { Pin::get_unchecked_mut(self) };
+        let q = unsafe { &mut *this.q };
+
+        if let State::Idle = this.state {
+            if let Ok(p) = unsafe { (*this.q).try_acquire(this.permits) } {
+                this.state = State::Done;
+                return Poll::Ready(p);
             }
-        });
 
-        Poll::Pending
+            unsafe { q.enqueue(&mut this.node) };
+            this.state = State::Waiting;
+        }
+
+        match this.state {
+            State::Waiting if this.node.value == 0 => {
+                unsafe { q.dequeue(&mut this.node) };
+                this.state = State::Done;
+                Poll::Ready(unsafe { SemaphorePermit::new(q, this.permits) })
+            }
+            State::Waiting => Poll::Pending,
+            _ => panic!("Acquire polled after completion"),
+        }
     }
 }
 
-impl<'a, const N: usize> Future for Acquire<'a, N> {
-    type Output = SemaphorePermit<'a, N>;
-
-    #[inline(always)]
-    fn poll(self: Pin<&mut Self>, cx: &mut core::task::Context<'_>) -> Poll<Self::Output> {
-        unsafe { Pin::get_unchecked_mut(self) }.poll(cx)
+impl<'a> Drop for Acquire<'a> {
+    fn drop(&mut self) {
+        if let State::Waiting = self.state {
+            let q = unsafe { &mut *self.q };
+            unsafe { q.dequeue(&mut self.node) };
+            q.release(self.permits - self.node.value);
+        }
     }
 }
 
@@ -118,7 +135,7 @@ impl<'a, const N: usize> Future for Acquire<'a, N> {
 //
 //     #[test]
 //     fn test() {
-//         let s = super::Semaphore::<2>::new(2);
+//         let s = super::Semaphore::new(2);
 //
 //         let mut fut1 = s.acquire_many(2);
 //         assert!(matches!(fut1.poll(), Poll::Ready(_)));
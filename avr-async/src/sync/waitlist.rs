@@ -0,0 +1,142 @@
+use core::{marker::PhantomPinned, ptr};
+
+/// A waiter stored inline in a pinned future and linked into a [`WaitList`].
+#[derive(Debug)]
+pub(crate) struct Node<T> {
+    pub value: T,
+    prev: *mut Node<T>,
+    next: *mut Node<T>,
+    linked: bool,
+    _pin: PhantomPinned,
+}
+
+impl<T> Node<T> {
+    #[inline(always)]
+    pub const fn new(value: T) -> Self {
+        Self {
+            value,
+            prev: ptr::null_mut(),
+            next: ptr::null_mut(),
+            linked: false,
+            _pin: PhantomPinned,
+        }
+    }
+
+    #[inline(always)]
+    pub const fn is_linked(&self) -> bool {
+        self.linked
+    }
+}
+
+/// Intrusive FIFO of [`Node`]s.
+///
+/// The list never owns its nodes: whoever pushes a node must keep it pinned and remove it before
+/// it is dropped.
+#[derive(Debug)]
+pub(crate) struct WaitList<T> {
+    head: *mut Node<T>,
+    tail: *mut Node<T>,
+}
+
+impl<T> WaitList<T> {
+    #[inline(always)]
+    pub const fn new() -> Self {
+        Self {
+            head: ptr::null_mut(),
+            tail: ptr::null_mut(),
+        }
+    }
+
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.head.is_null()
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.iter().count()
+    }
+
+    /// # Safety
+    /// `node` must be pinned, not linked in any list and must outlive its membership.
+    pub unsafe fn push_back(&mut self, node: *mut Node<T>) {
+        let n = &mut *node;
+        debug_assert!(!n.linked);
+
+        n.prev = self.tail;
+        n.next = ptr::null_mut();
+        n.linked = true;
+
+        if self.tail.is_null() {
+            self.head = node;
+        } else {
+            (*self.tail).next = node;
+        }
+        self.tail = node;
+    }
+
+    /// # Safety
+    /// `node` must be linked in this list.
+    pub unsafe fn remove(&mut self, node: *mut Node<T>) {
+        let n = &mut *node;
+        debug_assert!(n.linked);
+
+        if n.prev.is_null() {
+            self.head = n.next;
+        } else {
+            (*n.prev).next = n.next;
+        }
+
+        if n.next.is_null() {
+            self.tail = n.prev;
+        } else {
+            (*n.next).prev = n.prev;
+        }
+
+        n.prev = ptr::null_mut();
+        n.next = ptr::null_mut();
+        n.linked = false;
+    }
+
+    #[inline]
+    pub fn pop_front(&mut self) -> Option<*mut Node<T>> {
+        if self.head.is_null() {
+            None
+        } else {
+            let node = self.head;
+            unsafe { self.remove(node) };
+            Some(node)
+        }
+    }
+
+    #[inline(always)]
+    pub fn iter(&self) -> Iter<T> {
+        Iter { node: self.head }
+    }
+}
+
+impl<T> Default for WaitList<T> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub(crate) struct Iter<T> {
+    node: *mut Node<T>,
+}
+
+impl<T> Iterator for Iter<T> {
+    type Item = *mut Node<T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.node.is_null() {
+            None
+        } else {
+            let node = self.node;
+            self.node = unsafe { (*node).next };
+            Some(node)
+        }
+    }
+}
@@ -0,0 +1,91 @@
+use super::{driver, Duration, Instant};
+
+/// Longest span waited on one deadline.
+const CHUNK_NS: u64 = 1_000_000_000;
+
+/// `DelayNs` for drivers written against embedded-hal, backed by the time driver.
+///
+/// The async variant sleeps, rounding up to the next timer tick. The blocking variant spins on
+/// the clock for whole ticks and counts CPU cycles for the rest. Both need the
+/// [`super::TimeDriver`] to be initialized.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Delay;
+
+impl Delay {
+    #[inline(always)]
+    pub const fn new() -> Self {
+        Self
+    }
+
+    fn block(&mut self, mut ns: u64) {
+        let cpu_hz = driver::tick_hz() * driver::PRESCALER as u64;
+
+        // Chunks of at most 1s keep `ns * cpu_hz` within u64 for any u32 `FREQ` and the deadline
+        // well inside the wrap range of `Instant`.
+        while ns != 0 {
+            let chunk = ns.min(CHUNK_NS);
+            ns -= chunk;
+
+            let cycles = chunk.saturating_mul(cpu_hz) / 1_000_000_000;
+            let ticks = cycles / driver::PRESCALER as u64;
+
+            if ticks != 0 {
+                let deadline = Instant::now() + Duration::from_ticks(ticks as super::Ticks);
+                while Instant::now() < deadline {}
+            }
+
+            spin((cycles % driver::PRESCALER as u64) as u16);
+        }
+    }
+}
+
+/// Busy-waits for about `cycles` CPU cycles, 4 cycles per iteration.
+#[inline(always)]
+fn spin(cycles: u16) {
+    let iterations = cycles / 4;
+
+    if iterations != 0 {
+        unsafe {
+            core::arch::asm!(
+                "1: sbiw {0}, 1",
+                "brne 1b",
+                inout(reg_iw) iterations => _,
+                options(nomem, nostack),
+            )
+        };
+    }
+}
+
+impl embedded_hal::delay::DelayNs for Delay {
+    #[inline]
+    fn delay_ns(&mut self, ns: u32) {
+        self.block(ns as u64)
+    }
+
+    #[inline]
+    fn delay_us(&mut self, us: u32) {
+        self.block(us as u64 * 1_000)
+    }
+
+    #[inline]
+    fn delay_ms(&mut self, ms: u32) {
+        self.block(ms as u64 * 1_000_000)
+    }
+}
+
+impl embedded_hal_async::delay::DelayNs for Delay {
+    #[inline]
+    async fn delay_ns(&mut self, ns: u32) {
+        super::sleep(Duration::from_micros(ns.div_ceil(1_000))).await
+    }
+
+    #[inline]
+    async fn delay_us(&mut self, us: u32) {
+        super::sleep(Duration::from_micros(us)).await
+    }
+
+    #[inline]
+    async fn delay_ms(&mut self, ms: u32) {
+        super::sleep(Duration::from_millis(ms)).await
+    }
+}
@@ -0,0 +1,586 @@
+use core::{
+    cmp::Ordering,
+    future::Future,
+    marker::PhantomData,
+    ops::{Add, AddAssign, Div, Mul, Sub, SubAssign},
+    pin::Pin,
+    task::Poll,
+};
+
+use avr_device::interrupt::{self, CriticalSection};
+use avr_hal_generic::clock::Clock;
+
+use super::queue::TimerQueue;
+use crate::{sync::waitlist::Node, SyncUnsafeCell};
+
+#[cfg(not(feature = "time64"))]
+pub type Ticks = u32;
+#[cfg(feature = "time64")]
+pub type Ticks = u64;
+
+#[cfg(not(feature = "time64"))]
+type SignedTicks = i32;
+#[cfg(feature = "time64")]
+type SignedTicks = i64;
+
+/// The timer runs at `CLOCK::FREQ / PRESCALER`.
+pub const PRESCALER: u32 = 64;
+
+macro_rules! impl_timer {
+    (
+        $TC:ident,
+        $width:ty,
+        $tccra:ident,
+        $tccrb:ident,
+        $tcnt:ident,
+        $ocra:ident,
+        $timsk:ident,
+        $tifr:ident,
+        overflow: $ovf:expr,
+        compare: $cmp:expr $(,)?
+    ) => {
+        /// The timer claimed by [`TimeDriver`].
+        pub type Timer = crate::hal::pac::$TC;
+
+        mod hw {
+            use super::Ticks;
+
+            pub(super) const BITS: u32 = <$width>::BITS;
+
+            // Interrupt enable and flag bits share the same position.
+            const OVF: u8 = $ovf;
+            const CMP: u8 = $cmp;
+
+            #[inline(always)]
+            pub(super) fn setup() {
+                let tc = unsafe { &*crate::hal::pac::$TC::ptr() };
+                tc.$tccra.write(|w| unsafe { w.bits(0) });
+                // Normal mode, clk/64
+                tc.$tccrb.write(|w| unsafe { w.bits(0b011) });
+                tc.$tcnt.write(|w| unsafe { w.bits(0) });
+                tc.$tifr.write(|w| unsafe { w.bits(OVF | CMP) });
+                tc.$timsk
+                    .modify(|r, w| unsafe { w.bits((r.bits() & !CMP) | OVF) });
+            }
+
+            #[inline(always)]
+            pub(super) fn counter() -> Ticks {
+                let tc = unsafe { &*crate::hal::pac::$TC::ptr() };
+                tc.$tcnt.read().bits() as Ticks
+            }
+
+            #[inline(always)]
+            pub(super) fn overflow_pending() -> bool {
+                let tc = unsafe { &*crate::hal::pac::$TC::ptr() };
+                tc.$tifr.read().bits() & OVF != 0
+            }
+
+            #[inline(always)]
+            pub(super) fn set_compare(val: Ticks) {
+                let tc = unsafe { &*crate::hal::pac::$TC::ptr() };
+                tc.$ocra.write(|w| unsafe { w.bits(val as $width) });
+                tc.$tifr.write(|w| unsafe { w.bits(CMP) });
+            }
+
+            #[inline(always)]
+            pub(super) fn enable_compare(enable: bool) {
+                let tc = unsafe { &*crate::hal::pac::$TC::ptr() };
+                tc.$timsk.modify(|r, w| unsafe {
+                    w.bits(if enable {
+                        r.bits() | CMP
+                    } else {
+                        r.bits() & !CMP
+                    })
+                });
+            }
+        }
+    };
+}
+
+#[cfg(any(
+    feature = "atmega1280",
+    feature = "atmega168",
+    feature = "atmega2560",
+    feature = "atmega328p",
+    feature = "atmega328pb",
+    feature = "atmega32u4",
+    feature = "atmega48p",
+    feature = "attiny84",
+    feature = "attiny88",
+    feature = "attiny167",
+))]
+impl_timer!(
+    TC1,
+    u16,
+    tccr1a,
+    tccr1b,
+    tcnt1,
+    ocr1a,
+    timsk1,
+    tifr1,
+    overflow: 0b0000_0001,
+    compare: 0b0000_0010,
+);
+
+// TC1 on the attiny85 is an 8-bit high speed timer, TC0 is just as good and leaves it free.
+#[cfg(feature = "attiny85")]
+impl_timer!(
+    TC0,
+    u8,
+    tccr0a,
+    tccr0b,
+    tcnt0,
+    ocr0a,
+    timsk,
+    tifr,
+    overflow: 0b0000_0010,
+    compare: 0b0001_0000,
+);
+
+const PERIOD: Ticks = 1 << hw::BITS;
+
+struct State {
+    /// Ticks counted up to the last handled overflow.
+    base: Ticks,
+    tick_hz: u32,
+    queue: TimerQueue<Instant>,
+}
+
+// Only ever accessed inside critical sections.
+unsafe impl Sync for State {}
+
+static STATE: SyncUnsafeCell<State> = SyncUnsafeCell::new(State {
+    base: 0,
+    tick_hz: 0,
+    queue: TimerQueue::new(),
+});
+
+#[inline(always)]
+fn state(_: &CriticalSection) -> &'static mut State {
+    unsafe { &mut *STATE.get() }
+}
+
+#[inline(always)]
+pub(crate) fn tick_hz() -> u64 {
+    interrupt::free(|cs| state(cs).tick_hz) as u64
+}
+
+#[inline]
+fn now(cs: &CriticalSection) -> Ticks {
+    let (base, counter) = now_parts(cs);
+    base.wrapping_add(counter)
+}
+
+/// The ticks at the start of the current overflow window and the counter value.
+#[inline]
+fn now_parts(cs: &CriticalSection) -> (Ticks, Ticks) {
+    let mut base = state(cs).base;
+    let counter = hw::counter();
+
+    // The counter wrapped but the overflow interrupt didn't run yet: the low value has been read
+    // after the wrap.
+    if hw::overflow_pending() && counter < PERIOD / 2 {
+        base = base.wrapping_add(PERIOD);
+    }
+
+    (base, counter)
+}
+
+/// Moves the clock forward by `ticks` counted while the timer was stopped.
+pub(crate) fn advance(ticks: Ticks, cs: &CriticalSection) {
+    let state = state(cs);
+    state.base = state.base.wrapping_add(ticks);
+    schedule(cs);
+}
+
+/// Expires due sleepers and programs the compare match for the earliest remaining deadline.
+///
+/// Deadlines beyond the current overflow window are armed by the overflow interrupt once their
+/// window comes.
+fn schedule(cs: &CriticalSection) -> bool {
+    let mut expired = false;
+
+    loop {
+        let (window, counter) = now_parts(cs);
+        let now = Instant(window.wrapping_add(counter));
+        let (exp, next) = state(cs).queue.expire(now);
+        expired |= exp;
+
+        match next {
+            Some(deadline) => {
+                let offset = deadline.0.wrapping_sub(window);
+                if offset >= PERIOD {
+                    hw::enable_compare(false);
+                    break;
+                }
+
+                hw::set_compare(offset);
+                hw::enable_compare(true);
+
+                // The counter may have already passed the compare value while it was written.
+                if Instant(self::now(cs)) < deadline {
+                    break;
+                }
+            }
+            None => {
+                hw::enable_compare(false);
+                break;
+            }
+        }
+    }
+
+    if expired {
+        unsafe { crate::executor::wake() };
+    }
+
+    expired
+}
+
+/// Owns the hardware timer backing [`Instant`] and [`sleep`].
+///
+/// Call [`TimeDriver::on_overflow`] and [`TimeDriver::on_compare`] from the timer overflow and
+/// compare match A vectors of your `Runtime` (`timer1_ovf`/`timer1_compa`, `timer0_ovf`/
+/// `timer0_compa` on the attiny85).
+pub struct TimeDriver<CLOCK> {
+    _timer: Timer,
+    _clock: PhantomData<CLOCK>,
+}
+
+impl<CLOCK: Clock> TimeDriver<CLOCK> {
+    /// Timer ticks per second.
+    pub const TICK_HZ: u32 = CLOCK::FREQ / PRESCALER;
+
+    pub fn new(timer: Timer, cs: &CriticalSection) -> Self {
+        let state = state(cs);
+        state.base = 0;
+        state.tick_hz = Self::TICK_HZ;
+        state.queue = TimerQueue::new();
+
+        hw::setup();
+
+        Self {
+            _timer: timer,
+            _clock: PhantomData,
+        }
+    }
+
+    #[inline]
+    pub fn on_overflow(&mut self, cs: &CriticalSection) -> bool {
+        let state = state(cs);
+        state.base = state.base.wrapping_add(PERIOD);
+
+        if state.queue.is_empty() {
+            false
+        } else {
+            schedule(cs)
+        }
+    }
+
+    #[inline]
+    pub fn on_compare(&mut self, cs: &CriticalSection) -> bool {
+        schedule(cs)
+    }
+}
+
+/// A point in time of the monotonic clock.
+///
+/// The clock wraps, comparisons are only meaningful between instants less than half of the
+/// [`Ticks`] range apart (about 2.4 hours at 16MHz with 32-bit ticks).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Instant(Ticks);
+
+impl Instant {
+    #[inline]
+    pub fn now() -> Self {
+        Self(interrupt::free(now))
+    }
+
+    #[inline(always)]
+    pub const fn from_ticks(ticks: Ticks) -> Self {
+        Self(ticks)
+    }
+
+    #[inline(always)]
+    pub const fn as_ticks(&self) -> Ticks {
+        self.0
+    }
+
+    #[inline]
+    pub fn elapsed(&self) -> Duration {
+        Self::now() - *self
+    }
+
+    #[inline]
+    pub fn duration_since(&self, earlier: Instant) -> Duration {
+        Duration(self.0.wrapping_sub(earlier.0))
+    }
+
+    #[inline]
+    pub fn checked_duration_since(&self, earlier: Instant) -> Option<Duration> {
+        if *self >= earlier {
+            Some(self.duration_since(earlier))
+        } else {
+            None
+        }
+    }
+}
+
+impl Ord for Instant {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.0.wrapping_sub(other.0) as SignedTicks).cmp(&0)
+    }
+}
+
+impl PartialOrd for Instant {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Add<Duration> for Instant {
+    type Output = Instant;
+
+    #[inline(always)]
+    fn add(self, rhs: Duration) -> Self::Output {
+        Self(self.0.wrapping_add(rhs.0))
+    }
+}
+
+impl AddAssign<Duration> for Instant {
+    #[inline(always)]
+    fn add_assign(&mut self, rhs: Duration) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub<Duration> for Instant {
+    type Output = Instant;
+
+    #[inline(always)]
+    fn sub(self, rhs: Duration) -> Self::Output {
+        Self(self.0.wrapping_sub(rhs.0))
+    }
+}
+
+impl SubAssign<Duration> for Instant {
+    #[inline(always)]
+    fn sub_assign(&mut self, rhs: Duration) {
+        *self = *self - rhs;
+    }
+}
+
+impl Sub<Instant> for Instant {
+    type Output = Duration;
+
+    #[inline(always)]
+    fn sub(self, rhs: Instant) -> Self::Output {
+        self.duration_since(rhs)
+    }
+}
+
+/// A span of time in timer ticks.
+///
+/// Conversions from and to real time units use the tick rate set by [`TimeDriver::new`]. Before
+/// that the rate is unknown, `from_*` give [`Duration::ZERO`] and `as_*` give 0;
+/// [`TimeDriver::duration`] converts at the rate of a clock known at compile time instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Duration(Ticks);
+
+impl Duration {
+    pub const ZERO: Duration = Duration(0);
+
+    #[inline(always)]
+    pub const fn from_ticks(ticks: Ticks) -> Self {
+        Self(ticks)
+    }
+
+    #[inline(always)]
+    pub const fn as_ticks(&self) -> Ticks {
+        self.0
+    }
+
+    #[inline]
+    pub fn from_secs(secs: u32) -> Self {
+        Self((secs as u64 * tick_hz()) as Ticks)
+    }
+
+    /// Rounds up to the next tick.
+    #[inline]
+    pub fn from_millis(millis: u32) -> Self {
+        Self((millis as u64 * tick_hz()).div_ceil(1000) as Ticks)
+    }
+
+    /// Rounds up to the next tick.
+    #[inline]
+    pub fn from_micros(micros: u32) -> Self {
+        Self((micros as u64 * tick_hz()).div_ceil(1_000_000) as Ticks)
+    }
+
+    #[inline]
+    pub fn as_secs(&self) -> u64 {
+        (self.0 as u64).checked_div(tick_hz()).unwrap_or(0)
+    }
+
+    #[inline]
+    pub fn as_millis(&self) -> u64 {
+        (self.0 as u64 * 1000).checked_div(tick_hz()).unwrap_or(0)
+    }
+
+    #[inline]
+    pub fn as_micros(&self) -> u64 {
+        (self.0 as u64 * 1_000_000)
+            .checked_div(tick_hz())
+            .unwrap_or(0)
+    }
+
+    #[inline(always)]
+    pub const fn is_zero(&self) -> bool {
+        self.0 == 0
+    }
+
+    #[inline(always)]
+    pub fn checked_add(self, rhs: Duration) -> Option<Duration> {
+        self.0.checked_add(rhs.0).map(Self)
+    }
+
+    #[inline(always)]
+    pub fn checked_sub(self, rhs: Duration) -> Option<Duration> {
+        self.0.checked_sub(rhs.0).map(Self)
+    }
+
+    #[inline(always)]
+    pub fn saturating_sub(self, rhs: Duration) -> Duration {
+        Self(self.0.saturating_sub(rhs.0))
+    }
+}
+
+impl Add for Duration {
+    type Output = Duration;
+
+    #[inline(always)]
+    fn add(self, rhs: Duration) -> Self::Output {
+        Self(self.0 + rhs.0)
+    }
+}
+
+impl AddAssign for Duration {
+    #[inline(always)]
+    fn add_assign(&mut self, rhs: Duration) {
+        self.0 += rhs.0;
+    }
+}
+
+impl Sub for Duration {
+    type Output = Duration;
+
+    #[inline(always)]
+    fn sub(self, rhs: Duration) -> Self::Output {
+        Self(self.0 - rhs.0)
+    }
+}
+
+impl SubAssign for Duration {
+    #[inline(always)]
+    fn sub_assign(&mut self, rhs: Duration) {
+        self.0 -= rhs.0;
+    }
+}
+
+impl Mul<Ticks> for Duration {
+    type Output = Duration;
+
+    #[inline(always)]
+    fn mul(self, rhs: Ticks) -> Self::Output {
+        Self(self.0 * rhs)
+    }
+}
+
+impl Div<Ticks> for Duration {
+    type Output = Duration;
+
+    #[inline(always)]
+    fn div(self, rhs: Ticks) -> Self::Output {
+        Self(self.0 / rhs)
+    }
+}
+
+/// Waits until `duration` has elapsed.
+#[inline]
+pub fn sleep(duration: Duration) -> Sleep {
+    Sleep::new(Instant::now() + duration)
+}
+
+/// Waits until `deadline` has been reached.
+#[inline(always)]
+pub fn sleep_until(deadline: Instant) -> Sleep {
+    Sleep::new(deadline)
+}
+
+pub struct Sleep {
+    node: Node<Instant>,
+}
+
+impl Sleep {
+    #[inline(always)]
+    pub const fn new(deadline: Instant) -> Self {
+        Self {
+            node: Node::new(deadline),
+        }
+    }
+
+    #[inline(always)]
+    pub const fn deadline(&self) -> Instant {
+        self.node.value
+    }
+
+    /// Changes the deadline, the sleep is pending again if it is in the future.
+    pub fn reset(self: Pin<&mut Self>, deadline: Instant) {
+        let this = unsafe { Pin::get_unchecked_mut(self) };
+
+        interrupt::free(|cs| {
+            this.node.value = deadline;
+            if this.node.is_linked() {
+                schedule(cs);
+            }
+        });
+    }
+
+    #[inline(always)]
+    pub fn is_elapsed(&self) -> bool {
+        Instant::now() >= self.node.value
+    }
+}
+
+impl Future for Sleep {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, _cx: &mut core::task::Context<'_>) -> Poll<Self::Output> {
+        let this = unsafe { Pin::get_unchecked_mut(self) };
+
+        interrupt::free(|cs| {
+            let queue = &mut state(cs).queue;
+
+            if Instant(now(cs)) >= this.node.value {
+                unsafe { queue.remove(&mut this.node) };
+                Poll::Ready(())
+            } else {
+                if !this.node.is_linked() {
+                    unsafe { queue.insert(&mut this.node) };
+                    schedule(cs);
+                }
+                Poll::Pending
+            }
+        })
+    }
+}
+
+impl Drop for Sleep {
+    #[inline]
+    fn drop(&mut self) {
+        if self.node.is_linked() {
+            interrupt::free(|cs| unsafe { state(cs).queue.remove(&mut self.node) });
+        }
+    }
+}
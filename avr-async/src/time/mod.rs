@@ -0,0 +1,264 @@
+pub mod delay;
+pub mod driver;
+mod queue;
+pub mod rate;
+#[cfg(any(
+    feature = "atmega1280",
+    feature = "atmega168",
+    feature = "atmega2560",
+    feature = "atmega328p",
+    feature = "atmega48p",
+))]
+pub mod rtc;
+pub mod timeout;
+pub mod wdt;
+
+use core::{future::Future, marker::PhantomData, task::Poll};
+
+use avr_device::interrupt::{self, CriticalSection};
+use num_traits::{
+    Bounded, CheckedAdd, NumAssignOps, One, Unsigned, WrappingAdd, WrappingMul, WrappingSub, Zero,
+};
+
+pub use delay::Delay;
+pub use driver::{sleep, sleep_until, Duration, Instant, Sleep, Ticks, TimeDriver, Timer};
+pub use rate::{TickDuration, TickInstant};
+pub use timeout::{timeout_at, with_timeout, Elapsed, Timeout};
+pub use wdt::deep_sleep;
+
+pub trait UInt:
+    Unsigned
+    + Copy
+    + NumAssignOps
+    + Ord
+    + Bounded
+    + CheckedAdd
+    + WrappingAdd
+    + WrappingSub
+    + WrappingMul
+{
+}
+
+impl<
+        I: Unsigned
+            + Copy
+            + NumAssignOps
+            + Ord
+            + Bounded
+            + CheckedAdd
+            + WrappingAdd
+            + WrappingSub
+            + WrappingMul,
+    > UInt for I
+{
+}
+
+pub struct TickCounter<I: UInt> {
+    counter: I,
+    snapshot: I,
+}
+
+impl<I: UInt> Default for TickCounter<I> {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            counter: Zero::zero(),
+            snapshot: Zero::zero(),
+        }
+    }
+}
+
+impl<I: UInt> TickCounter<I> {
+    #[inline(always)]
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// # Safety
+    /// This function is marked as unsafe to remember you to call it in a critical section (usually
+    /// an interrupt)
+    #[inline(always)]
+    pub unsafe fn inc(&mut self) {
+        self.counter = self.counter.wrapping_add(&One::one());
+    }
+
+    #[inline(always)]
+    pub fn get(&self) -> I {
+        self.snapshot
+    }
+
+    #[inline]
+    pub fn get_real(&self) -> I {
+        interrupt::free(|_cs| self.counter)
+    }
+
+    #[inline(always)]
+    pub fn delay<'a, 'b: 'a>(&'b self, delay: I) -> TickDelay<'a, I> {
+        TickDelay::new(self, delay)
+    }
+
+    #[inline(always)]
+    pub fn interval<'a, 'b: 'a>(&'b self, interval: I) -> TickInterval<'a, I> {
+        TickInterval::new(self, interval)
+    }
+
+    #[inline(always)]
+    pub fn snapshot(&mut self, _cs: &CriticalSection) {
+        self.snapshot = self.counter;
+    }
+}
+
+#[inline(always)]
+fn elapsed<I: UInt>(start: I, now: I) -> Option<I> {
+    if start > now {
+        (I::max_value() - start).checked_add(&now)
+    } else {
+        Some(now - start)
+    }
+}
+
+pub struct TickDelay<'a, I: UInt> {
+    start: I,
+    counter: *const TickCounter<I>,
+    delay: I,
+    _life: PhantomData<&'a ()>,
+}
+
+impl<'a, I: UInt> TickDelay<'a, I> {
+    #[inline(always)]
+    pub fn new<'b: 'a>(tick: &'b TickCounter<I>, delay: I) -> Self {
+        TickDelay {
+            start: tick.get(),
+            counter: tick as *const _,
+            delay,
+            _life: PhantomData,
+        }
+    }
+}
+
+impl<'a, I: UInt> Future for TickDelay<'a, I> {
+    type Output = ();
+
+    fn poll(
+        self: core::pin::Pin<&mut Self>,
+        _cx: &mut core::task::Context<'_>,
+    ) -> core::task::Poll<Self::Output> {
+        if elapsed(self.start, unsafe { &*self.counter }.get())
+            .map(|x| x >= self.delay)
+            .unwrap_or(true)
+        {
+            Poll::Ready(())
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+/// What a [`TickInterval`] does with the ticks it missed because the task was late.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MissedTickBehavior {
+    /// Fire every missed tick as fast as possible, then go back to the original schedule.
+    #[default]
+    Burst,
+    /// Restart the schedule one period after the late tick.
+    Delay,
+    /// Fire once and skip to the next tick of the original schedule.
+    Skip,
+}
+
+/// Periodic ticks on absolute deadlines, a late poll doesn't shift the following ticks.
+pub struct TickInterval<'a, I: UInt> {
+    counter: *const TickCounter<I>,
+    period: I,
+    deadline: I,
+    behavior: MissedTickBehavior,
+    _life: PhantomData<&'a ()>,
+}
+
+impl<'a, I: UInt> TickInterval<'a, I> {
+    /// The first tick fires `interval` ticks from now.
+    #[inline(always)]
+    pub fn new<'b: 'a>(tick: &'b TickCounter<I>, interval: I) -> Self {
+        assert!(
+            interval > Zero::zero(),
+            "TickInterval period must be non-zero"
+        );
+
+        TickInterval {
+            counter: tick as *const _,
+            period: interval,
+            deadline: tick.get().wrapping_add(&interval),
+            behavior: MissedTickBehavior::default(),
+            _life: PhantomData,
+        }
+    }
+
+    #[inline(always)]
+    pub const fn period(&self) -> I {
+        self.period
+    }
+
+    #[inline(always)]
+    pub const fn missed_tick_behavior(&self) -> MissedTickBehavior {
+        self.behavior
+    }
+
+    #[inline(always)]
+    pub fn set_missed_tick_behavior(&mut self, behavior: MissedTickBehavior) {
+        self.behavior = behavior;
+    }
+
+    /// Restarts the schedule, the next tick fires one period from now.
+    #[inline]
+    pub fn reset(&mut self) {
+        self.deadline = unsafe { &*self.counter }.get().wrapping_add(&self.period);
+    }
+
+    /// Resolves to the tick count the tick was scheduled at.
+    #[allow(clippy::should_implement_trait)]
+    #[inline(always)]
+    pub fn next(&mut self) -> Tick<'_, 'a, I> {
+        Tick { interval: self }
+    }
+
+    pub fn poll_tick(&mut self) -> Poll<I> {
+        let now = unsafe { &*self.counter }.get();
+
+        if !reached(self.deadline, now) {
+            return Poll::Pending;
+        }
+
+        let tick = self.deadline;
+        self.deadline = match self.behavior {
+            MissedTickBehavior::Burst => tick.wrapping_add(&self.period),
+            MissedTickBehavior::Delay => now.wrapping_add(&self.period),
+            MissedTickBehavior::Skip => {
+                let missed = now.wrapping_sub(&tick) / self.period;
+                tick.wrapping_add(&(missed + One::one()).wrapping_mul(&self.period))
+            }
+        };
+
+        Poll::Ready(tick)
+    }
+}
+
+#[inline(always)]
+fn reached<I: UInt>(deadline: I, now: I) -> bool {
+    now.wrapping_sub(&deadline) <= I::max_value() / (I::one() + I::one())
+}
+
+pub struct Tick<'a, 'b, I: UInt> {
+    interval: &'a mut TickInterval<'b, I>,
+}
+
+impl<'a, 'b, I: UInt> Future for Tick<'a, 'b, I> {
+    type Output = I;
+
+    #[inline]
+    fn poll(
+        mut self: core::pin::Pin<&mut Self>,
+        _cx: &mut core::task::Context<'_>,
+    ) -> core::task::Poll<Self::Output> {
+        self.interval.poll_tick()
+    }
+}
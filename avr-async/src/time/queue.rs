@@ -0,0 +1,63 @@
+use crate::sync::waitlist::{Node, WaitList};
+
+/// Pending sleeps, each one linked through a node stored in its pinned future.
+///
+/// Only the earliest deadline is programmed in the hardware, so a single compare match serves
+/// every sleeper.
+pub(crate) struct TimerQueue<T> {
+    waiters: WaitList<T>,
+}
+
+impl<T: Ord + Copy> TimerQueue<T> {
+    #[inline(always)]
+    pub const fn new() -> Self {
+        Self {
+            waiters: WaitList::new(),
+        }
+    }
+
+    /// # Safety
+    /// `node` must be pinned and removed before it is dropped.
+    #[inline]
+    pub unsafe fn insert(&mut self, node: *mut Node<T>) {
+        if !(*node).is_linked() {
+            self.waiters.push_back(node);
+        }
+    }
+
+    /// # Safety
+    /// `node` must not be linked in any other list.
+    #[inline]
+    pub unsafe fn remove(&mut self, node: *mut Node<T>) {
+        if (*node).is_linked() {
+            self.waiters.remove(node);
+        }
+    }
+
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.waiters.is_empty()
+    }
+
+    /// Unlinks every sleeper whose deadline is not after `now`.
+    ///
+    /// Returns whether any sleeper expired and the earliest deadline still pending.
+    pub fn expire(&mut self, now: T) -> (bool, Option<T>) {
+        let mut expired = false;
+        let mut next: Option<T> = None;
+
+        // The iterator moves past a node before yielding it, unlinking it is fine.
+        for node in self.waiters.iter() {
+            let deadline = unsafe { (*node).value };
+
+            if deadline <= now {
+                unsafe { self.waiters.remove(node) };
+                expired = true;
+            } else if next.map(|next| deadline < next).unwrap_or(true) {
+                next = Some(deadline);
+            }
+        }
+
+        (expired, next)
+    }
+}
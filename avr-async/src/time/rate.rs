@@ -0,0 +1,55 @@
+use avr_hal_generic::clock::Clock;
+
+use super::{Duration, Instant, Ticks, TimeDriver};
+
+/// A [`fugit::Duration`] counted in timer ticks, e.g.
+/// `TickDuration<{ TimeDriver::<MHz16>::TICK_HZ }>::millis(5)` is computed at compile time.
+pub type TickDuration<const HZ: u32> = fugit::Duration<Ticks, 1, HZ>;
+
+/// A [`fugit::Instant`] counted in timer ticks.
+pub type TickInstant<const HZ: u32> = fugit::Instant<Ticks, 1, HZ>;
+
+/// Conversions from and to [`fugit`] types at the tick rate of `CLOCK`.
+impl<CLOCK: Clock> TimeDriver<CLOCK> {
+    /// Rounds up to the next tick.
+    #[inline]
+    pub const fn duration<const NOM: u32, const DENOM: u32>(
+        d: fugit::Duration<Ticks, NOM, DENOM>,
+    ) -> Duration {
+        if NOM == 1 && DENOM == Self::TICK_HZ {
+            Duration::from_ticks(d.ticks())
+        } else {
+            let num = d.ticks() as u64 * NOM as u64 * Self::TICK_HZ as u64;
+            Duration::from_ticks(num.div_ceil(DENOM as u64) as Ticks)
+        }
+    }
+
+    /// Rounds down to the unit of the target.
+    #[inline]
+    pub const fn fugit_duration<const NOM: u32, const DENOM: u32>(
+        d: Duration,
+    ) -> fugit::Duration<Ticks, NOM, DENOM> {
+        if NOM == 1 && DENOM == Self::TICK_HZ {
+            fugit::Duration::<Ticks, NOM, DENOM>::from_ticks(d.as_ticks())
+        } else {
+            let den = NOM as u64 * Self::TICK_HZ as u64;
+            fugit::Duration::<Ticks, NOM, DENOM>::from_ticks(
+                (d.as_ticks() as u64 * DENOM as u64 / den) as Ticks,
+            )
+        }
+    }
+
+    /// Instants can't be rescaled, `HZ` other than [`Self::TICK_HZ`] fails to compile.
+    #[inline(always)]
+    pub const fn instant<const HZ: u32>(i: TickInstant<HZ>) -> Instant {
+        const { assert!(HZ == Self::TICK_HZ, "HZ is not the tick rate of CLOCK") };
+        Instant::from_ticks(i.ticks())
+    }
+
+    /// Instants can't be rescaled, `HZ` other than [`Self::TICK_HZ`] fails to compile.
+    #[inline(always)]
+    pub const fn tick_instant<const HZ: u32>(i: Instant) -> TickInstant<HZ> {
+        const { assert!(HZ == Self::TICK_HZ, "HZ is not the tick rate of CLOCK") };
+        TickInstant::from_ticks(i.as_ticks())
+    }
+}
@@ -0,0 +1,299 @@
+use core::{future::Future, pin::Pin, task::Poll};
+
+use avr_device::interrupt::{self, CriticalSection};
+
+use super::queue::TimerQueue;
+use crate::{sync::waitlist::Node, SyncUnsafeCell};
+
+// ASSR
+const AS2: u8 = 1 << 5;
+const TCN2UB: u8 = 1 << 4;
+const OCR2AUB: u8 = 1 << 3;
+const OCR2BUB: u8 = 1 << 2;
+const TCR2AUB: u8 = 1 << 1;
+const TCR2BUB: u8 = 1 << 0;
+const BUSY: u8 = TCN2UB | OCR2AUB | OCR2BUB | TCR2AUB | TCR2BUB;
+
+// TIMSK2/TIFR2
+const TOV2: u8 = 1 << 0;
+
+/// Seconds elapsed since 1970-01-01 00:00:00.
+pub type Timestamp = u32;
+
+struct State {
+    seconds: Timestamp,
+    alarms: TimerQueue<Timestamp>,
+}
+
+// Only ever accessed inside critical sections.
+unsafe impl Sync for State {}
+
+static STATE: SyncUnsafeCell<State> = SyncUnsafeCell::new(State {
+    seconds: 0,
+    alarms: TimerQueue::new(),
+});
+
+#[inline(always)]
+fn state(_: &CriticalSection) -> &'static mut State {
+    unsafe { &mut *STATE.get() }
+}
+
+#[inline(always)]
+fn tc() -> &'static crate::hal::pac::tc2::RegisterBlock {
+    unsafe { &*crate::hal::pac::TC2::ptr() }
+}
+
+/// Waits for the writes to the asynchronous registers to reach the 32kHz clock domain.
+#[inline(always)]
+fn wait_update() {
+    while tc().assr.read().bits() & BUSY != 0 {}
+}
+
+/// Wall clock driven by a 32.768kHz crystal on TOSC1/TOSC2.
+///
+/// TC2 runs from the crystal with a /128 prescaler and overflows once per second, it keeps
+/// counting in power-save mode. Call [`RtcDriver::on_overflow`] from the `timer2_ovf` vector of
+/// your `Runtime`.
+pub struct RtcDriver {
+    _timer: crate::hal::pac::TC2,
+}
+
+impl RtcDriver {
+    pub fn new(timer: crate::hal::pac::TC2, now: DateTime, cs: &CriticalSection) -> Self {
+        let tc = tc();
+
+        tc.timsk2.write(|w| unsafe { w.bits(0) });
+        tc.assr.write(|w| unsafe { w.bits(AS2) });
+        tc.tcnt2.write(|w| unsafe { w.bits(0) });
+        tc.tccr2a.write(|w| unsafe { w.bits(0) });
+        // Normal mode, clk/128
+        tc.tccr2b.write(|w| unsafe { w.bits(0b101) });
+        wait_update();
+        tc.tifr2.write(|w| unsafe { w.bits(0xff) });
+        tc.timsk2.write(|w| unsafe { w.bits(TOV2) });
+
+        let state = state(cs);
+        state.seconds = now.timestamp();
+        state.alarms = TimerQueue::new();
+
+        Self { _timer: timer }
+    }
+
+    #[inline]
+    pub fn on_overflow(&mut self, cs: &CriticalSection) -> bool {
+        let state = state(cs);
+        state.seconds = state.seconds.wrapping_add(1);
+
+        let (expired, _) = state.alarms.expire(state.seconds);
+        if expired {
+            unsafe { crate::executor::wake() };
+        }
+        expired
+    }
+
+    /// Must be called before entering power-save mode after waking up from a TC2 interrupt,
+    /// otherwise the chip may go back to sleep before the interrupt logic is reset.
+    /// [`super::deep_sleep`] already does.
+    #[inline(always)]
+    pub fn sync(&self) {
+        resync();
+    }
+}
+
+#[inline]
+pub(super) fn resync() {
+    let tc = tc();
+    tc.tccr2a.write(|w| unsafe { w.bits(0) });
+    while tc.assr.read().bits() & TCR2AUB != 0 {}
+}
+
+/// Whether TC2 runs from the crystal, set up by [`RtcDriver::new`].
+#[inline(always)]
+pub(super) fn is_running() -> bool {
+    tc().assr.read().bits() & AS2 != 0
+}
+
+#[inline]
+pub fn timestamp() -> Timestamp {
+    interrupt::free(|cs| state(cs).seconds)
+}
+
+#[inline]
+pub fn now() -> DateTime {
+    DateTime::from_timestamp(timestamp())
+}
+
+/// Sets the clock, the current second restarts from its beginning.
+pub fn set(now: DateTime) {
+    interrupt::free(|cs| {
+        tc().tcnt2.write(|w| unsafe { w.bits(0) });
+        wait_update();
+        // An overflow pending from before would count the new second as already elapsed.
+        tc().tifr2.write(|w| unsafe { w.bits(TOV2) });
+
+        let state = state(cs);
+        state.seconds = now.timestamp();
+
+        let (expired, _) = state.alarms.expire(state.seconds);
+        if expired {
+            unsafe { crate::executor::wake() };
+        }
+    })
+}
+
+/// Waits until the clock reaches `at`. Setting the clock past it fires the alarm too.
+#[inline(always)]
+pub fn sleep_until(at: DateTime) -> Alarm {
+    Alarm::new(at.timestamp())
+}
+
+pub struct Alarm {
+    node: Node<Timestamp>,
+}
+
+impl Alarm {
+    #[inline(always)]
+    pub const fn new(at: Timestamp) -> Self {
+        Self {
+            node: Node::new(at),
+        }
+    }
+
+    #[inline(always)]
+    pub fn at(&self) -> DateTime {
+        DateTime::from_timestamp(self.node.value)
+    }
+}
+
+impl Future for Alarm {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, _cx: &mut core::task::Context<'_>) -> Poll<Self::Output> {
+        let this = unsafe { Pin::get_unchecked_mut(self) };
+
+        interrupt::free(|cs| {
+            let state = state(cs);
+
+            if state.seconds >= this.node.value {
+                unsafe { state.alarms.remove(&mut this.node) };
+                Poll::Ready(())
+            } else {
+                unsafe { state.alarms.insert(&mut this.node) };
+                Poll::Pending
+            }
+        })
+    }
+}
+
+impl Drop for Alarm {
+    #[inline]
+    fn drop(&mut self) {
+        if self.node.is_linked() {
+            interrupt::free(|cs| unsafe { state(cs).alarms.remove(&mut self.node) });
+        }
+    }
+}
+
+/// A UTC date and time, valid from 1970 to 2106.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DateTime {
+    pub year: u16,
+    pub month: u8,
+    pub day: u8,
+    pub hour: u8,
+    pub minute: u8,
+    pub second: u8,
+}
+
+impl DateTime {
+    #[inline(always)]
+    pub const fn new(year: u16, month: u8, day: u8, hour: u8, minute: u8, second: u8) -> Self {
+        Self {
+            year,
+            month,
+            day,
+            hour,
+            minute,
+            second,
+        }
+    }
+
+    pub const fn from_timestamp(ts: Timestamp) -> Self {
+        let days = (ts / 86400) as i32;
+        let secs = ts % 86400;
+        let (year, month, day) = civil_from_days(days);
+
+        Self {
+            year: year as u16,
+            month,
+            day,
+            hour: (secs / 3600) as u8,
+            minute: (secs / 60 % 60) as u8,
+            second: (secs % 60) as u8,
+        }
+    }
+
+    pub const fn timestamp(&self) -> Timestamp {
+        let days = days_from_civil(self.year as i32, self.month, self.day);
+
+        (days as u32) * 86400
+            + self.hour as u32 * 3600
+            + self.minute as u32 * 60
+            + self.second as u32
+    }
+
+    /// ISO weekday, from 1 (Monday) to 7 (Sunday).
+    #[inline]
+    pub const fn weekday(&self) -> u8 {
+        // 1970-01-01 was a Thursday.
+        ((days_from_civil(self.year as i32, self.month, self.day) + 3) % 7 + 1) as u8
+    }
+
+    pub const fn is_valid(&self) -> bool {
+        self.year >= 1970
+            && self.month >= 1
+            && self.month <= 12
+            && self.day >= 1
+            && self.day <= days_in_month(self.year, self.month)
+            && self.hour < 24
+            && self.minute < 60
+            && self.second < 60
+    }
+}
+
+pub const fn is_leap_year(year: u16) -> bool {
+    year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+}
+
+pub const fn days_in_month(year: u16, month: u8) -> u8 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+// Howard Hinnant's algorithms, with years starting in March so the leap day comes last.
+const fn days_from_civil(year: i32, month: u8, day: u8) -> i32 {
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let m = month as i32;
+    let doy = (153 * (if m > 2 { m - 3 } else { m + 9 }) + 2) / 5 + day as i32 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146097 + doe - 719468
+}
+
+const fn civil_from_days(days: i32) -> (i32, u8, u8) {
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u8;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u8;
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
@@ -0,0 +1,57 @@
+use core::{future::Future, pin::Pin, task::Poll};
+
+use super::{sleep_until, Duration, Instant, Sleep};
+
+/// The deadline of a [`Timeout`] was reached before its future completed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Elapsed;
+
+/// Runs `fut` for at most `duration`.
+#[inline]
+pub fn with_timeout<F: Future>(duration: Duration, fut: F) -> Timeout<F> {
+    timeout_at(Instant::now() + duration, fut)
+}
+
+/// Runs `fut` until `deadline` at most.
+#[inline(always)]
+pub fn timeout_at<F: Future>(deadline: Instant, fut: F) -> Timeout<F> {
+    Timeout {
+        fut,
+        sleep: sleep_until(deadline),
+    }
+}
+
+pub struct Timeout<F: Future> {
+    fut: F,
+    sleep: Sleep,
+}
+
+impl<F: Future> Timeout<F> {
+    #[inline(always)]
+    pub const fn deadline(&self) -> Instant {
+        self.sleep.deadline()
+    }
+
+    #[inline(always)]
+    pub fn get_ref(&self) -> &F {
+        &self.fut
+    }
+}
+
+impl<F: Future> Future for Timeout<F> {
+    type Output = Result<F::Output, Elapsed>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut core::task::Context<'_>) -> Poll<Self::Output> {
+        let this = unsafe { Pin::get_unchecked_mut(self) };
+
+        // The future gets a chance to complete even if the deadline already passed.
+        if let Poll::Ready(res) = unsafe { Pin::new_unchecked(&mut this.fut) }.poll(cx) {
+            return Poll::Ready(Ok(res));
+        }
+
+        match unsafe { Pin::new_unchecked(&mut this.sleep) }.poll(cx) {
+            Poll::Ready(()) => Poll::Ready(Err(Elapsed)),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
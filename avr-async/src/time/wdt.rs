@@ -0,0 +1,179 @@
+use avr_device::interrupt::{self, CriticalSection};
+
+use super::{driver, Duration, Instant};
+use crate::SyncUnsafeCell;
+
+// WDTCSR/WDTCR
+const WDIE: u8 = 1 << 6;
+const WDCE: u8 = 1 << 4;
+const WDE: u8 = 1 << 3;
+const WDP3: u8 = 1 << 5;
+
+// MCUSR
+const WDRF: u8 = 1 << 3;
+
+macro_rules! impl_sleep {
+    (
+        $wdtcsr:ident,
+        $sleep:ident,
+        power_down: $pdown:expr,
+        $(power_save: $psave:expr,)?
+        mask: $mask:expr $(,)?
+    ) => {
+        mod hw {
+            pub(super) const POWER_DOWN: u8 = $pdown;
+            $(pub(super) const POWER_SAVE: u8 = $psave;)?
+
+            #[inline(always)]
+            pub(super) fn watchdog(bits: u8) {
+                let wdt = unsafe { &*crate::hal::pac::WDT::ptr() };
+                // Timed sequence, the new value must be written within 4 cycles.
+                wdt.$wdtcsr
+                    .write(|w| unsafe { w.bits(super::WDCE | super::WDE) });
+                wdt.$wdtcsr.write(|w| unsafe { w.bits(bits) });
+            }
+
+            #[inline(always)]
+            pub(super) fn clear_reset_flag() {
+                let cpu = unsafe { &*crate::hal::pac::CPU::ptr() };
+                cpu.mcusr
+                    .modify(|r, w| unsafe { w.bits(r.bits() & !super::WDRF) });
+            }
+
+            /// Enables sleeping in `mode`, 0 disables sleep.
+            #[inline(always)]
+            pub(super) fn sleep_mode(mode: u8) {
+                let cpu = unsafe { &*crate::hal::pac::CPU::ptr() };
+                cpu.$sleep
+                    .modify(|r, w| unsafe { w.bits((r.bits() & !$mask) | mode) });
+            }
+        }
+    };
+}
+
+#[cfg(any(
+    feature = "atmega1280",
+    feature = "atmega168",
+    feature = "atmega2560",
+    feature = "atmega328p",
+    feature = "atmega48p",
+))]
+impl_sleep!(
+    wdtcsr,
+    smcr,
+    power_down: 0b0000_0101,
+    power_save: 0b0000_0111,
+    mask: 0b0000_1111,
+);
+
+#[cfg(any(feature = "atmega328pb", feature = "atmega32u4", feature = "attiny88"))]
+impl_sleep!(wdtcsr, smcr, power_down: 0b0000_0101, mask: 0b0000_1111);
+
+#[cfg(feature = "attiny167")]
+impl_sleep!(wdtcr, smcr, power_down: 0b0000_0101, mask: 0b0000_1111);
+
+#[cfg(feature = "attiny84")]
+impl_sleep!(wdtcsr, mcucr, power_down: 0b0011_0000, mask: 0b0011_1000);
+
+#[cfg(feature = "attiny85")]
+impl_sleep!(wdtcr, mcucr, power_down: 0b0011_0000, mask: 0b0011_1000);
+
+/// Shortest watchdog period, the longest one is 2^9 times as long (8.192s).
+const MIN_PERIOD_MS: u32 = 16;
+
+static FIRED: SyncUnsafeCell<bool> = SyncUnsafeCell::new(false);
+
+#[inline(always)]
+fn fired(_: &CriticalSection) -> &'static mut bool {
+    unsafe { &mut *FIRED.get() }
+}
+
+/// Must be called from the `wdt` vector of your `Runtime` for [`deep_sleep`] to work.
+#[inline]
+pub fn on_watchdog(cs: &CriticalSection) -> bool {
+    *fired(cs) = true;
+    false
+}
+
+/// Power-save keeps TC2 counting for a running [`super::rtc::RtcDriver`], power-down stops it.
+#[inline(always)]
+fn sleep_mode() -> u8 {
+    #[cfg(any(
+        feature = "atmega1280",
+        feature = "atmega168",
+        feature = "atmega2560",
+        feature = "atmega328p",
+        feature = "atmega48p",
+    ))]
+    if super::rtc::is_running() {
+        return hw::POWER_SAVE;
+    }
+    hw::POWER_DOWN
+}
+
+/// Puts the chip in power-down mode for about `duration`, with the watchdog as wake-up source.
+/// Power-save is used instead while the [`super::rtc::RtcDriver`] runs, so the wall clock keeps
+/// counting.
+///
+/// The time is slept in chunks of 16ms to 8s, the remainder below 16ms is not slept at all. Other
+/// interrupts are still served, but no task runs until the whole duration has elapsed. The timer
+/// behind [`super::Instant`] is stopped in power-down, it is moved forward afterwards by the
+/// nominal watchdog periods less the ticks it counted while awake: the watchdog oscillator is not
+/// calibrated, expect a few percent of error.
+///
+/// Needs the [`super::TimeDriver`] to be initialized, before that `duration` reads as zero and
+/// nothing is slept.
+pub fn deep_sleep(duration: Duration) {
+    let mut remaining = duration.as_millis();
+
+    while remaining >= MIN_PERIOD_MS as u64 {
+        let mut prescaler = 0u8;
+        while prescaler < 9 && (MIN_PERIOD_MS as u64) << (prescaler + 1) <= remaining {
+            prescaler += 1;
+        }
+        let period = (MIN_PERIOD_MS as u64) << prescaler;
+
+        let bits = WDIE | (prescaler & 0b111) | if prescaler & 0b1000 != 0 { WDP3 } else { 0 };
+
+        let start = interrupt::free(|cs| {
+            *fired(cs) = false;
+            hw::watchdog(bits);
+            hw::sleep_mode(sleep_mode());
+            Instant::now()
+        });
+
+        loop {
+            unsafe { core::arch::asm!("cli") };
+            let cs = unsafe { CriticalSection::new() };
+            if *fired(&cs) {
+                break;
+            }
+            #[cfg(any(
+                feature = "atmega1280",
+                feature = "atmega168",
+                feature = "atmega2560",
+                feature = "atmega328p",
+                feature = "atmega48p",
+            ))]
+            if super::rtc::is_running() {
+                super::rtc::resync();
+            }
+            // `sei` takes effect after `sleep`, a pending interrupt can't slip in between.
+            unsafe { core::arch::asm!("sei", "sleep") };
+        }
+
+        {
+            let cs = unsafe { CriticalSection::new() };
+            hw::sleep_mode(0);
+            // A set WDRF overrides WDE, it must be cleared first.
+            hw::clear_reset_flag();
+            hw::watchdog(0);
+            // The timer ran while other interrupts were served between sleeps.
+            let slept = Duration::from_millis(period as u32).saturating_sub(start.elapsed());
+            driver::advance(slept.as_ticks(), &cs);
+        }
+        unsafe { core::arch::asm!("sei") };
+
+        remaining -= period;
+    }
+}
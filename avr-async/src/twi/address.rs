@@ -1,11 +1,19 @@
+/// A 7-bit or 10-bit device address.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[non_exhaustive]
-pub struct Address(u8);
+pub struct Address {
+    addr: u16,
+    ten_bit: bool,
+}
 
 impl core::fmt::Display for Address {
     #[inline(always)]
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        core::fmt::Display::fmt(&self.0, f)
+        core::fmt::Display::fmt(&self.addr, f)?;
+        if self.ten_bit {
+            f.write_str(" (10-bit)")?;
+        }
+        Ok(())
     }
 }
 
@@ -14,32 +22,99 @@ impl core::fmt::Display for Address {
 pub struct InvalidAddress;
 
 impl Address {
+    /// `11110xx` starts a 10-bit address, it is not a 7-bit one.
+    #[inline(always)]
+    const fn is_seven_bit(me: u8) -> bool {
+        me <= 0b01111111 && me & 0b1111100 != 0b1111000
+    }
+
     #[inline]
     pub const fn new(me: u8) -> Result<Self, InvalidAddress> {
-        if me > 0b01111111 {
+        if !Self::is_seven_bit(me) {
             Err(InvalidAddress)
         } else {
-            Ok(Self(me))
+            Ok(Self {
+                addr: me as u16,
+                ten_bit: false,
+            })
         }
     }
 
     #[inline]
     pub const fn const_new(me: u8) -> Self {
-        if me > 0b01111111 {
+        if !Self::is_seven_bit(me) {
             panic!("Invalid address")
         } else {
-            Self(me)
+            Self {
+                addr: me as u16,
+                ten_bit: false,
+            }
+        }
+    }
+
+    #[inline]
+    pub const fn new_ten_bit(me: u16) -> Result<Self, InvalidAddress> {
+        if me > 0b11_11111111 {
+            Err(InvalidAddress)
+        } else {
+            Ok(Self {
+                addr: me,
+                ten_bit: true,
+            })
+        }
+    }
+
+    #[inline]
+    pub const fn const_new_ten_bit(me: u16) -> Self {
+        if me > 0b11_11111111 {
+            panic!("Invalid address")
+        } else {
+            Self {
+                addr: me,
+                ten_bit: true,
+            }
+        }
+    }
+
+    #[inline(always)]
+    pub const fn is_ten_bit(&self) -> bool {
+        self.ten_bit
+    }
+
+    #[inline(always)]
+    pub const fn value(&self) -> u16 {
+        self.addr
+    }
+
+    /// The 7 bits sent with the R/W bit: the address itself, or `11110xx` with the two high bits
+    /// of a 10-bit address.
+    #[inline]
+    pub const fn header(&self) -> u8 {
+        if self.ten_bit {
+            0b1111000 | (self.addr >> 8) as u8
+        } else {
+            self.addr as u8
+        }
+    }
+
+    /// The low byte of a 10-bit address, sent as data right after the header.
+    #[inline]
+    pub const fn low_byte(&self) -> Option<u8> {
+        if self.ten_bit {
+            Some(self.addr as u8)
+        } else {
+            None
         }
     }
 
     #[inline]
     pub const fn as_write_byte(&self) -> u8 {
-        self.0 << 1
+        self.header() << 1
     }
 
     #[inline]
     pub const fn as_read_byte(&self) -> u8 {
-        (self.0 << 1) | 1
+        (self.header() << 1) | 1
     }
 }
 
@@ -52,10 +127,16 @@ impl TryFrom<u8> for Address {
     }
 }
 
-#[allow(clippy::from_over_into)]
-impl Into<u8> for Address {
+/// Only 7-bit addresses fit, e.g. as the own address of a slave.
+impl TryFrom<Address> for u8 {
+    type Error = InvalidAddress;
+
     #[inline(always)]
-    fn into(self) -> u8 {
-        self.0
+    fn try_from(value: Address) -> Result<Self, Self::Error> {
+        if value.ten_bit {
+            Err(InvalidAddress)
+        } else {
+            Ok(value.addr as u8)
+        }
     }
 }
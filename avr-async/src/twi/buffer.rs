@@ -8,6 +8,12 @@ pub trait InputBuffer {
     fn push(&mut self, byte: u8);
 
     fn is_last(&mut self) -> bool;
+
+    /// Ends the read without requesting another byte, only needed for buffers that can be empty.
+    #[inline(always)]
+    fn is_full(&mut self) -> bool {
+        false
+    }
 }
 
 pub struct SliceOutputBuffer<'a> {
@@ -78,6 +84,11 @@ impl<'a> InputBuffer for SliceInputBuffer<'a> {
     fn is_last(&mut self) -> bool {
         self.iter.len() == 1
     }
+
+    #[inline]
+    fn is_full(&mut self) -> bool {
+        self.iter.len() == 0
+    }
 }
 
 pub trait IntoInputBuffer {
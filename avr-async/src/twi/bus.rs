@@ -0,0 +1,116 @@
+use embedded_hal::i2c::{AddressMode, ErrorKind, ErrorType, Operation, SevenBitAddress};
+use embedded_hal_async::i2c::I2c;
+
+use crate::sync::Mutex;
+
+/// Owns a bus and hands out an [`I2cDevice`] for every device on it, like `embedded-hal-bus`.
+///
+/// Every transaction locks the bus until it completes, devices can be driven from different tasks.
+pub struct SharedBus<BUS> {
+    bus: Mutex<BUS>,
+}
+
+impl<BUS> SharedBus<BUS> {
+    #[inline(always)]
+    pub const fn new(bus: BUS) -> Self {
+        Self {
+            bus: Mutex::new(bus),
+        }
+    }
+
+    #[inline(always)]
+    pub fn device<A: AddressMode>(&self, address: A) -> I2cDevice<BUS, A> {
+        I2cDevice {
+            bus: &self.bus,
+            address,
+        }
+    }
+}
+
+/// A device on a [`SharedBus`], bound to its address.
+///
+/// The inherent methods talk to the bound address. Drivers written against [`I2c`] pass the
+/// address themselves, any other than the bound one fails with [`DeviceError::AddressMismatch`].
+pub struct I2cDevice<'a, BUS, A = SevenBitAddress> {
+    bus: &'a Mutex<BUS>,
+    address: A,
+}
+
+impl<'a, BUS: I2c<A>, A: AddressMode + Copy> I2cDevice<'a, BUS, A> {
+    #[inline(always)]
+    pub fn address(&self) -> A {
+        self.address
+    }
+
+    #[inline]
+    pub async fn read(&mut self, read: &mut [u8]) -> Result<(), BUS::Error> {
+        self.bus.lock().await.read(self.address, read).await
+    }
+
+    #[inline]
+    pub async fn write(&mut self, write: &[u8]) -> Result<(), BUS::Error> {
+        self.bus.lock().await.write(self.address, write).await
+    }
+
+    #[inline]
+    pub async fn write_read(&mut self, write: &[u8], read: &mut [u8]) -> Result<(), BUS::Error> {
+        self.bus
+            .lock()
+            .await
+            .write_read(self.address, write, read)
+            .await
+    }
+
+    #[inline]
+    pub async fn transaction(
+        &mut self,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), BUS::Error> {
+        self.bus
+            .lock()
+            .await
+            .transaction(self.address, operations)
+            .await
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeviceError<E> {
+    Bus(E),
+    /// The address is not the one the device is bound to, nothing went on the bus.
+    AddressMismatch,
+}
+
+impl<E: embedded_hal::i2c::Error> embedded_hal::i2c::Error for DeviceError<E> {
+    #[inline]
+    fn kind(&self) -> ErrorKind {
+        match self {
+            Self::Bus(err) => err.kind(),
+            Self::AddressMismatch => ErrorKind::Other,
+        }
+    }
+}
+
+impl<BUS: ErrorType, A> ErrorType for I2cDevice<'_, BUS, A> {
+    type Error = DeviceError<BUS::Error>;
+}
+
+impl<BUS: I2c<A>, A: AddressMode + Copy + PartialEq> I2c<A> for I2cDevice<'_, BUS, A> {
+    #[inline]
+    async fn transaction(
+        &mut self,
+        address: A,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        if address != self.address {
+            return Err(DeviceError::AddressMismatch);
+        }
+
+        self.bus
+            .lock()
+            .await
+            .transaction(address, operations)
+            .await
+            .map_err(DeviceError::Bus)
+    }
+}
@@ -1,19 +1,330 @@
-pub struct Driver<TWI: super::peripheral::TwiOps<SDA, SCL>, SDA, SCL, CLOCK> {
-    state: Option<TransactionState>,
-    set: bool,
-    peripheral: super::peripheral::TwiPeripheral<TWI, SDA, SCL, CLOCK>,
+use core::ptr;
+
+use avr_device::interrupt::CriticalSection;
+
+use super::{
+    buffer::{InputBuffer, OutputBuffer},
+    peripheral::{Direction, Error, TwiOps, TwiPeripheral},
+    transaction::{RawAction, Request},
+};
+use crate::{
+    slab::{SlabBox, Slabbed},
+    sync::waitlist::{Node, WaitList},
+};
+
+/// Busy loop iterations to wait for a STOP condition before resetting the peripheral.
+const STOP_TIMEOUT: u32 = 1024;
+
+/// Queue side of the engine, reached by [`super::Twi`] without knowing the peripheral types.
+pub(crate) trait Engine {
+    /// # Safety
+    /// `node` must be pinned, not linked, and stay alive until it completes or is cancelled.
+    unsafe fn enqueue(&mut self, node: *mut Node<Request>, cs: &CriticalSection);
+
+    /// # Safety
+    /// `node` must have been enqueued on this engine.
+    unsafe fn cancel(&mut self, node: *mut Node<Request>, cs: &CriticalSection);
+}
+
+pub(crate) enum TransactionState {
+    Idle,
+    Start(RawAction),
+    Address(RawAction),
+    /// Second byte of a 10-bit address.
+    LowByte(RawAction),
+    /// Repeated START before the read header of a 10-bit address.
+    Restart(RawAction),
+    ReadHeader(RawAction),
+    Write(*mut dyn OutputBuffer),
+    Read {
+        buf: *mut dyn InputBuffer,
+        last: bool,
+    },
+    /// A byte clocked for a read with no room, NACKed and dropped.
+    Discard,
+    /// The transaction was dropped mid-flight, the bus is released on the next interrupt.
+    Aborted,
+}
+
+pub struct TwiSlab<TWI: TwiOps<SDA, SCL>, SDA, SCL, CLOCK> {
+    peripheral: TwiPeripheral<TWI, SDA, SCL, CLOCK>,
+    queue: WaitList<Request>,
+    current: *mut Node<Request>,
+    state: TransactionState,
 }
 
-impl<TWI: super::peripheral::TwiOps<SDA, SCL>, SDA, SCL, CLOCK> Driver<TWI, SDA, SCL, CLOCK> {
-    #[allow(clippy::new_without_default)]
+impl<TWI: TwiOps<SDA, SCL>, SDA, SCL, CLOCK> TwiSlab<TWI, SDA, SCL, CLOCK> {
     #[inline(always)]
-    pub(crate) fn new(peripheral: super::peripheral::TwiPeripheral<TWI, SDA, SCL, CLOCK>) -> Self {
+    pub fn new(peripheral: TwiPeripheral<TWI, SDA, SCL, CLOCK>) -> Self {
         Self {
-            state: None,
-            set: false,
             peripheral,
+            queue: WaitList::new(),
+            current: ptr::null_mut(),
+            state: TransactionState::Idle,
+        }
+    }
+
+    /// Starts the first queued transaction if the bus is free, returns whether a transaction
+    /// completed without touching the bus.
+    fn start_next(&mut self) -> bool {
+        let mut completed = false;
+
+        while self.current.is_null() && matches!(self.state, TransactionState::Idle) {
+            let Some(node) = self.queue.pop_front() else {
+                break;
+            };
+            self.current = node;
+
+            match unsafe { self.next_action() } {
+                Some(action) => {
+                    self.peripheral.send_start();
+                    self.state = TransactionState::Start(action);
+                }
+                None => {
+                    self.finish(Ok(()));
+                    completed = true;
+                }
+            }
+        }
+
+        completed
+    }
+
+    /// # Safety
+    /// `self.current` must not be null.
+    unsafe fn next_action(&mut self) -> Option<RawAction> {
+        let transaction = (*self.current).value.transaction?;
+        (*transaction.as_ptr()).next().map(RawAction::new)
+    }
+
+    fn finish(&mut self, result: Result<(), Error>) -> bool {
+        unsafe { (*self.current).value.result = Some(result) };
+        self.current = ptr::null_mut();
+        self.state = TransactionState::Idle;
+        true
+    }
+
+    fn fail(&mut self, err: Error) -> bool {
+        // Another master owns the bus, it is not ours to stop.
+        if matches!(err, Error::ArbitrationLost) {
+            self.peripheral.release();
+        } else {
+            self.peripheral.stop(STOP_TIMEOUT);
+        }
+        self.finish(Err(err))
+    }
+
+    /// Moves to the next action of the current transaction with a repeated START, or ends it.
+    fn advance(&mut self) -> bool {
+        match unsafe { self.next_action() } {
+            Some(action) => {
+                self.peripheral.send_start();
+                self.state = TransactionState::Start(action);
+                false
+            }
+            None => {
+                self.peripheral.stop(STOP_TIMEOUT);
+                self.finish(Ok(()))
+            }
+        }
+    }
+
+    /// The device acknowledged its address, moves on to the data.
+    fn begin(&mut self, action: RawAction) -> bool {
+        match action {
+            RawAction::Write(_, buf) => self.write(buf),
+            // The slave drives SDA once it acknowledged SLA+R, it only lets go after a NACKed
+            // byte, a read with no room still clocks one.
+            RawAction::Read(_, buf) if unsafe { (*buf).is_full() } => {
+                self.peripheral.send_read(true);
+                self.state = TransactionState::Discard;
+                false
+            }
+            RawAction::Read(_, buf) => self.read(buf),
+        }
+    }
+
+    fn write(&mut self, buf: *mut dyn OutputBuffer) -> bool {
+        match unsafe { (*buf).next() } {
+            Some(byte) => {
+                self.peripheral.send_write(byte);
+                self.state = TransactionState::Write(buf);
+                false
+            }
+            None => self.advance(),
+        }
+    }
+
+    fn read(&mut self, buf: *mut dyn InputBuffer) -> bool {
+        if unsafe { (*buf).is_full() } {
+            self.advance()
+        } else {
+            let last = unsafe { (*buf).is_last() };
+            self.peripheral.send_read(last);
+            self.state = TransactionState::Read { buf, last };
+            false
         }
     }
+
+    fn step(&mut self) -> bool {
+        match core::mem::replace(&mut self.state, TransactionState::Idle) {
+            TransactionState::Idle => {
+                self.peripheral.release();
+                false
+            }
+            TransactionState::Aborted => {
+                self.peripheral.stop(STOP_TIMEOUT);
+                false
+            }
+            TransactionState::Start(action) => match self.peripheral.recv_start() {
+                Ok(()) => {
+                    let address = action.address();
+                    // A 10-bit read is addressed for writing first.
+                    let direction = match action {
+                        RawAction::Read(..) if !address.is_ten_bit() => Direction::Read,
+                        _ => Direction::Write,
+                    };
+                    self.peripheral.send_slarw(address.header(), direction);
+                    self.state = TransactionState::Address(action);
+                    false
+                }
+                Err(err) => self.fail(err),
+            },
+            TransactionState::Address(action) => match self.peripheral.recv_slarw() {
+                Ok(()) => match action.address().low_byte() {
+                    Some(low) => {
+                        self.peripheral.send_write(low);
+                        self.state = TransactionState::LowByte(action);
+                        false
+                    }
+                    None => self.begin(action),
+                },
+                Err(err) => self.fail(err),
+            },
+            TransactionState::LowByte(action) => match self.peripheral.recv_write() {
+                Ok(()) => match action {
+                    RawAction::Write(..) => self.begin(action),
+                    RawAction::Read(..) => {
+                        self.peripheral.send_start();
+                        self.state = TransactionState::Restart(action);
+                        false
+                    }
+                },
+                Err(Error::DataNack) => self.fail(Error::AddressNack),
+                Err(err) => self.fail(err),
+            },
+            TransactionState::Restart(action) => match self.peripheral.recv_start() {
+                Ok(()) => {
+                    self.peripheral
+                        .send_slarw(action.address().header(), Direction::Read);
+                    self.state = TransactionState::ReadHeader(action);
+                    false
+                }
+                Err(err) => self.fail(err),
+            },
+            TransactionState::ReadHeader(action) => match self.peripheral.recv_slarw() {
+                Ok(()) => self.begin(action),
+                Err(err) => self.fail(err),
+            },
+            TransactionState::Write(buf) => match self.peripheral.recv_write() {
+                Ok(()) => self.write(buf),
+                Err(err) => self.fail(err),
+            },
+            TransactionState::Read { buf, last } => match self.peripheral.recv_read() {
+                Ok(byte) => {
+                    unsafe { (*buf).push(byte) };
+                    if last {
+                        self.advance()
+                    } else {
+                        self.read(buf)
+                    }
+                }
+                Err(err) => self.fail(err),
+            },
+            TransactionState::Discard => match self.peripheral.recv_read() {
+                Ok(_) => self.advance(),
+                Err(err) => self.fail(err),
+            },
+        }
+    }
+}
+
+impl<TWI: TwiOps<SDA, SCL>, SDA, SCL, CLOCK> Engine for TwiSlab<TWI, SDA, SCL, CLOCK> {
+    unsafe fn enqueue(&mut self, node: *mut Node<Request>, _: &CriticalSection) {
+        self.queue.push_back(node);
+        if self.start_next() {
+            crate::executor::wake();
+        }
+    }
+
+    unsafe fn cancel(&mut self, node: *mut Node<Request>, _: &CriticalSection) {
+        if (*node).is_linked() {
+            self.queue.remove(node);
+        } else if self.current == node {
+            // The buffers go away with the node, the ISR must not touch them anymore.
+            self.current = ptr::null_mut();
+            self.state = TransactionState::Aborted;
+        }
+    }
+}
+
+/// Interrupt side of the engine, call [`Driver::run`] from the `twi` vector of your `Runtime`.
+pub struct Driver<TWI: TwiOps<SDA, SCL>, SDA, SCL, CLOCK> {
+    inner: SlabBox<TwiSlab<TWI, SDA, SCL, CLOCK>>,
+}
+
+impl<TWI: TwiOps<SDA, SCL>, SDA, SCL, CLOCK> Slabbed for Driver<TWI, SDA, SCL, CLOCK> {
+    type InnerType = TwiSlab<TWI, SDA, SCL, CLOCK>;
+}
+
+impl<TWI: TwiOps<SDA, SCL>, SDA, SCL, CLOCK> Driver<TWI, SDA, SCL, CLOCK> {
+    #[inline(always)]
+    pub(crate) fn new(inner: SlabBox<TwiSlab<TWI, SDA, SCL, CLOCK>>) -> Self {
+        Self { inner }
+    }
+
+    /// Walks the current transaction one bus event further, returns whether a transaction
+    /// completed.
+    #[inline]
+    pub fn run(&mut self, _: &CriticalSection) -> bool {
+        let inner = &mut *self.inner;
+
+        if !inner.peripheral.is_ready() {
+            return false;
+        }
+
+        let completed = inner.step() | inner.start_next();
+        if completed {
+            unsafe { crate::executor::wake() };
+        }
+        completed
+    }
+}
+
+impl<TWI: TwiOps<SDA, SCL>, SDA, SCL, CLOCK> FnOnce<(&CriticalSection,)>
+    for Driver<TWI, SDA, SCL, CLOCK>
+{
+    type Output = bool;
+
+    #[inline(always)]
+    extern "rust-call" fn call_once(mut self, (cs,): (&CriticalSection,)) -> Self::Output {
+        self.run(cs)
+    }
+}
+
+impl<TWI: TwiOps<SDA, SCL>, SDA, SCL, CLOCK> FnMut<(&CriticalSection,)>
+    for Driver<TWI, SDA, SCL, CLOCK>
+{
+    #[inline(always)]
+    extern "rust-call" fn call_mut(&mut self, (cs,): (&CriticalSection,)) -> Self::Output {
+        self.run(cs)
+    }
 }
 
-pub struct TransactionState;
+#[cfg(feature = "atmega328p")]
+pub type Driver1<CLOCK> = Driver<
+    super::peripheral::TwiPeripheral1Pac,
+    crate::hal::port::Pin<crate::hal::port::mode::Input, super::peripheral::TwiPeripheral1Sda>,
+    crate::hal::port::Pin<crate::hal::port::mode::Input, super::peripheral::TwiPeripheral1Scl>,
+    CLOCK,
+>;
@@ -1,25 +1,76 @@
 mod address;
 mod buffer;
+pub mod bus;
 pub mod driver;
 pub mod peripheral;
 mod transaction;
 
-pub use address::Address;
-use driver::Driver;
+use core::ptr::NonNull;
+
+pub use address::{Address, InvalidAddress};
+use avr_device::interrupt::CriticalSection;
+pub use buffer::*;
+pub use bus::{DeviceError, I2cDevice, SharedBus};
+pub use driver::Driver;
+use driver::{Engine, TwiSlab};
+pub use peripheral::Error;
 pub use transaction::*;
 
-pub struct Twi;
+use crate::{slab::Slab, sync::waitlist::Node};
+
+/// Handle to a TWI bus, copy it to every task that talks on the bus.
+///
+/// Transactions are queued and run one after the other by the [`Driver`], each
+/// [`TransactionWait`] resolves with the result of its own transaction.
+#[derive(Clone, Copy)]
+pub struct Twi {
+    engine: NonNull<dyn Engine>,
+}
 
 impl Twi {
-    pub fn register(
-        &mut self,
-        _transaction: &mut dyn Transaction,
-        _result: &mut Option<Result<(), ()>>,
-    ) {
-        unimplemented!()
+    #[inline(always)]
+    pub fn new<TWI, SDA, SCL, CLOCK>(
+        slab: Slab<Driver<TWI, SDA, SCL, CLOCK>>,
+        peripheral: peripheral::TwiPeripheral<TWI, SDA, SCL, CLOCK>,
+    ) -> (Self, Driver<TWI, SDA, SCL, CLOCK>)
+    where
+        TWI: peripheral::TwiOps<SDA, SCL> + 'static,
+        SDA: 'static,
+        SCL: 'static,
+        CLOCK: 'static,
+    {
+        let mut inner = slab.get(TwiSlab::new(peripheral));
+        let engine = inner.as_ptr_mut() as *mut dyn Engine;
+
+        (
+            Self {
+                engine: unsafe { NonNull::new_unchecked(engine) },
+            },
+            Driver::new(inner),
+        )
+    }
+
+    #[inline(always)]
+    pub fn transaction<'a, 'b, T: Transaction<'a>>(
+        &'b mut self,
+        t: T,
+    ) -> TransactionWait<'a, 'b, T> {
+        TransactionWait::new(t, self)
+    }
+
+    /// # Safety
+    /// `node` must be pinned and unregistered before it is dropped.
+    #[inline(always)]
+    pub(crate) unsafe fn register(&mut self, node: *mut Node<Request>, cs: &CriticalSection) {
+        self.engine.as_mut().enqueue(node, cs)
     }
 
-    pub fn unregister(&mut self, _transaction: &mut dyn Transaction) {
-        unimplemented!()
+    /// Removes a queued transaction, or aborts it if it is on the bus.
+    ///
+    /// # Safety
+    /// `node` must have been registered on this bus.
+    #[inline(always)]
+    pub(crate) unsafe fn unregister(&mut self, node: *mut Node<Request>, cs: &CriticalSection) {
+        self.engine.as_mut().cancel(node, cs)
     }
 }
@@ -1,9 +1,14 @@
 use super::{
     buffer::{InputBuffer, OutputBuffer},
+    peripheral::Error,
     Address, Twi,
 };
 
-use core::{future::Future, marker::PhantomData, pin::Pin, task::Poll};
+use core::{future::Future, marker::PhantomData, pin::Pin, ptr::NonNull, task::Poll};
+
+use avr_device::interrupt;
+
+use crate::sync::waitlist::Node;
 
 #[repr(C)]
 pub enum Action<'a> {
@@ -18,6 +23,18 @@ pub(crate) enum RawAction {
 }
 
 impl RawAction {
+    #[inline(always)]
+    pub(crate) fn new(a: Action<'_>) -> Self {
+        unsafe { ::core::mem::transmute(a) }
+    }
+
+    #[inline(always)]
+    pub(crate) const fn address(&self) -> Address {
+        match self {
+            Self::Write(address, _) | Self::Read(address, _) => *address,
+        }
+    }
+
     pub(crate) fn from_action(a: &Action<'_>) -> *const Self {
         unsafe { ::core::mem::transmute(a) }
     }
@@ -51,10 +68,16 @@ impl<'a> Transaction<'a> for SingleActionTransaction<'a> {
     }
 }
 
+/// A queued transaction, linked into the engine queue by its [`TransactionWait`].
+pub(crate) struct Request {
+    /// Lifetime erased, the future unregisters itself before the transaction goes away.
+    pub transaction: Option<NonNull<dyn Transaction<'static>>>,
+    pub result: Option<Result<(), Error>>,
+}
+
 pub struct TransactionWait<'a, 'b, T: Transaction<'a>> {
     t: T,
-    result: Option<Result<(), ()>>,
-    registered: bool,
+    node: Node<Request>,
     twi: &'b mut Twi,
     _life: PhantomData<&'a ()>,
 }
@@ -66,8 +89,10 @@ impl<'a, 'b, T: Transaction<'a>> TransactionWait<'a, 'b, T> {
     pub fn new(t: T, twi: &'b mut Twi) -> Self {
         TransactionWait {
             t,
-            result: None,
-            registered: false,
+            node: Node::new(Request {
+                transaction: None,
+                result: None,
+            }),
             twi,
             _life: PhantomData,
         }
@@ -75,29 +100,30 @@ impl<'a, 'b, T: Transaction<'a>> TransactionWait<'a, 'b, T> {
 }
 
 impl<'a, 'b, T: Transaction<'a>> Future for TransactionWait<'a, 'b, T> {
-    type Output = Result<(), ()>;
+    type Output = Result<(), Error>;
 
     fn poll(self: Pin<&mut Self>, _: &mut core::task::Context<'_>) -> Poll<Self::Output> {
         let this = unsafe { Pin::get_unchecked_mut(self) };
 
-        loop {
-            if !this.registered {
-                this.twi
-                    .register(&mut this.t as &mut dyn Transaction, &mut this.result);
-                this.registered = true;
-            } else {
-                break if this.result.is_some() {
-                    Poll::Ready(unsafe { this.result.take().unwrap_unchecked() })
-                } else {
-                    Poll::Pending
-                };
+        interrupt::free(|cs| {
+            if this.node.value.transaction.is_none() {
+                let t = NonNull::from(&mut this.t as &mut dyn Transaction<'a>);
+                this.node.value.transaction = Some(unsafe { core::mem::transmute(t) });
+                unsafe { this.twi.register(&mut this.node, cs) };
             }
-        }
+
+            match this.node.value.result.take() {
+                Some(result) => Poll::Ready(result),
+                None => Poll::Pending,
+            }
+        })
     }
 }
 
 impl<'a, 'b, T: Transaction<'a>> Drop for TransactionWait<'a, 'b, T> {
     fn drop(&mut self) {
-        self.twi.unregister(&mut self.t as &mut dyn Transaction);
+        if self.node.value.transaction.is_some() {
+            interrupt::free(|cs| unsafe { self.twi.unregister(&mut self.node, cs) });
+        }
     }
 }
@@ -0,0 +1,44 @@
+use embedded_hal::i2c::{ErrorKind, NoAcknowledgeSource};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    /// Another master won the bus.
+    ArbitrationLost,
+    /// No device acknowledged the address.
+    AddressNack,
+    /// The device refused a data byte.
+    DataNack,
+    /// Illegal START or STOP condition on the bus.
+    BusError,
+    /// The operation timed out, a slave held the bus and it was cleared.
+    BusStuck,
+    /// The address doesn't fit the addressing mode.
+    InvalidAddress,
+    Unknown,
+}
+
+impl From<super::peripheral::Error> for Error {
+    #[inline]
+    fn from(err: super::peripheral::Error) -> Self {
+        match err {
+            super::peripheral::Error::ArbitrationLost => Self::ArbitrationLost,
+            super::peripheral::Error::AddressNack => Self::AddressNack,
+            super::peripheral::Error::DataNack => Self::DataNack,
+            super::peripheral::Error::BusError => Self::BusError,
+            super::peripheral::Error::Unknown => Self::Unknown,
+        }
+    }
+}
+
+impl embedded_hal::i2c::Error for Error {
+    #[inline]
+    fn kind(&self) -> ErrorKind {
+        match self {
+            Self::ArbitrationLost => ErrorKind::ArbitrationLoss,
+            Self::AddressNack => ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address),
+            Self::DataNack => ErrorKind::NoAcknowledge(NoAcknowledgeSource::Data),
+            Self::BusError | Self::BusStuck => ErrorKind::Bus,
+            Self::InvalidAddress | Self::Unknown => ErrorKind::Other,
+        }
+    }
+}
@@ -0,0 +1,128 @@
+use core::{
+    future::{poll_fn, Future},
+    pin::pin,
+    task::Poll,
+};
+
+use embedded_hal::i2c::{ErrorType, Operation, SevenBitAddress, TenBitAddress};
+
+use super::{
+    peripheral::{Direction, TwiOps},
+    raw, Address, Deadline, Error, TwoWireInterface,
+};
+
+impl<TWI: TwiOps<SDA, SCL>, SDA, SCL, CLOCK> ErrorType for TwoWireInterface<TWI, SDA, SCL, CLOCK> {
+    type Error = Error;
+}
+
+impl<TWI: TwiOps<SDA, SCL>, SDA, SCL, CLOCK> embedded_hal_async::i2c::I2c<SevenBitAddress>
+    for TwoWireInterface<TWI, SDA, SCL, CLOCK>
+{
+    #[inline]
+    async fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        let address = Address::new(address).map_err(|_| Error::InvalidAddress)?;
+        transaction(&mut self.inner, address, operations).await
+    }
+}
+
+impl<TWI: TwiOps<SDA, SCL>, SDA, SCL, CLOCK> embedded_hal_async::i2c::I2c<TenBitAddress>
+    for TwoWireInterface<TWI, SDA, SCL, CLOCK>
+{
+    #[inline]
+    async fn transaction(
+        &mut self,
+        address: u16,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        let address = Address::new_ten_bit(address).map_err(|_| Error::InvalidAddress)?;
+        transaction(&mut self.inner, address, operations).await
+    }
+}
+
+/// Runs the whole transaction within the timeout of the interface, the bus is cleared if it
+/// expires.
+pub(crate) async fn transaction<TWI: TwiOps<SDA, SCL>, SDA, SCL, CLOCK>(
+    twi: &mut raw::TwoWireInterface<TWI, SDA, SCL, CLOCK>,
+    address: Address,
+    operations: &mut [Operation<'_>],
+) -> Result<(), Error> {
+    let mut deadline = pin!(Deadline::new(twi));
+
+    let res = {
+        let mut fut = pin!(run(twi, address, operations));
+        poll_fn(|cx| match fut.as_mut().poll(cx) {
+            Poll::Ready(res) => Poll::Ready(Some(res)),
+            Poll::Pending if deadline.as_mut().poll_elapsed(cx) => Poll::Ready(None),
+            Poll::Pending => Poll::Pending,
+        })
+        .await
+    };
+
+    match res {
+        Some(res) => res,
+        None => Err(twi.recover()),
+    }
+}
+
+/// Operations of the same direction share a START and an address, only the last byte of the last
+/// read of a group is not acknowledged. Empty reads are skipped, a read can't end on the bus
+/// without clocking a byte. A single STOP ends the transaction, also on error.
+async fn run<TWI: TwiOps<SDA, SCL>, SDA, SCL, CLOCK>(
+    twi: &mut raw::TwoWireInterface<TWI, SDA, SCL, CLOCK>,
+    address: Address,
+    operations: &mut [Operation<'_>],
+) -> Result<(), Error> {
+    let mut direction = None;
+    let mut started = false;
+
+    let res: Result<(), Error> = async {
+        let mut operations = operations;
+
+        while let Some((op, rest)) = core::mem::take(&mut operations).split_first_mut() {
+            operations = rest;
+            if is_empty_read(op) {
+                continue;
+            }
+
+            let dir = match op {
+                Operation::Write(_) => Direction::Write,
+                Operation::Read(_) => Direction::Read,
+            };
+            if direction != Some(dir) {
+                twi.start().await?;
+                started = true;
+                twi.sla_rw(address, dir).await?;
+                direction = Some(dir);
+            }
+
+            match op {
+                Operation::Write(buf) => twi.write(buf).await?,
+                Operation::Read(buf) => {
+                    let last = !matches!(
+                        operations.iter().find(|op| !is_empty_read(op)),
+                        Some(Operation::Read(_))
+                    );
+                    twi.read(buf, last).await?
+                }
+            }
+        }
+
+        Ok(())
+    }
+    .await;
+
+    // Nothing went on the bus when all the operations were empty reads.
+    if started {
+        twi.stop().await;
+    }
+    res
+}
+
+#[inline(always)]
+fn is_empty_read(op: &Operation<'_>) -> bool {
+    matches!(op, Operation::Read(buf) if buf.is_empty())
+}
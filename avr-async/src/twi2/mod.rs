@@ -1,22 +1,30 @@
+mod error;
+mod i2c;
 pub mod peripheral;
 mod raw;
 pub mod read;
+#[cfg(not(any(feature = "attiny84", feature = "attiny85", feature = "attiny167")))]
+pub mod slave;
 pub mod transaction;
+#[cfg(any(feature = "attiny84", feature = "attiny85", feature = "attiny167"))]
+mod usi;
 pub mod write;
 
-use core::{future::Future, mem::MaybeUninit};
+use core::{future::Future, mem::MaybeUninit, pin::Pin, task::Context};
 
-pub use address::Address;
 use avr_device::interrupt::CriticalSection;
 use avr_hal_generic::port;
-pub use peripheral::Error;
+pub use error::Error;
 
 use crate::slab::{Slab, SlabBox, Slabbed};
+pub use crate::twi::{Address, DeviceError, I2cDevice, InvalidAddress, SharedBus};
 
 pub struct TwiSlab<TWI: self::peripheral::TwiOps<SDA, SCL>, SDA, SCL, CLOCK> {
     peripheral: self::peripheral::TwiPeripheral<TWI, SDA, SCL, CLOCK>,
     command: MaybeUninit<State>,
     set: bool,
+    #[cfg(feature = "time")]
+    timeout: Option<crate::time::Duration>,
 }
 
 impl<TWI: self::peripheral::TwiOps<SDA, SCL>, SDA, SCL, CLOCK> TwiSlab<TWI, SDA, SCL, CLOCK> {
@@ -27,13 +35,29 @@ impl<TWI: self::peripheral::TwiOps<SDA, SCL>, SDA, SCL, CLOCK> TwiSlab<TWI, SDA,
             peripheral,
             command: MaybeUninit::uninit(),
             set: false,
+            #[cfg(feature = "time")]
+            timeout: None,
         }
     }
 }
 
+/// Progress of the address phase, a 10-bit address takes up to four bus events.
+#[derive(Clone, Copy)]
+pub(crate) enum SlaStep {
+    Header,
+    LowByte,
+    Restart,
+    ReadHeader,
+}
+
 pub(crate) enum State {
     Start(Option<Result<(), Error>>),
-    SlaRw(Option<Result<(), Error>>),
+    SlaRw {
+        address: Address,
+        direction: peripheral::Direction,
+        step: SlaStep,
+        res: Option<Result<(), Error>>,
+    },
     Write {
         buf: *const [u8],
         idx: usize,
@@ -42,9 +66,25 @@ pub(crate) enum State {
     Read {
         buf: *mut [u8],
         idx: usize,
+        /// The buffer ends a read, its last byte is not acknowledged.
+        last: bool,
         res: Option<Result<(), Error>>,
     },
     Stop(Option<()>),
+    #[cfg(not(any(feature = "attiny84", feature = "attiny85", feature = "attiny167")))]
+    Listen(Option<Result<(peripheral::Direction, bool), Error>>),
+    #[cfg(not(any(feature = "attiny84", feature = "attiny85", feature = "attiny167")))]
+    SlaveReceive {
+        buf: *mut [u8],
+        idx: usize,
+        res: Option<Result<usize, Error>>,
+    },
+    #[cfg(not(any(feature = "attiny84", feature = "attiny85", feature = "attiny167")))]
+    SlaveTransmit {
+        buf: *const [u8],
+        idx: usize,
+        res: Option<Result<usize, Error>>,
+    },
 }
 
 pub struct TwoWireInterfaceDriver<TWI: self::peripheral::TwiOps<SDA, SCL>, SDA, SCL, CLOCK> {
@@ -64,13 +104,70 @@ impl<TWI: self::peripheral::TwiOps<SDA, SCL>, SDA, SCL, CLOCK>
         if self.inner.peripheral.is_ready() && self.inner.set {
             let res = match unsafe { &mut *(self.inner.command.as_mut_ptr()) } {
                 State::Start(ref mut res) => {
-                    *res = Some(self.inner.peripheral.recv_start());
-                    true
-                }
-                State::SlaRw(ref mut res) => {
-                    *res = Some(self.inner.peripheral.recv_slarw());
+                    *res = Some(self.inner.peripheral.recv_start().map_err(Into::into));
                     true
                 }
+                State::SlaRw {
+                    address,
+                    direction,
+                    ref mut step,
+                    ref mut res,
+                } => match *step {
+                    SlaStep::Header => match self.inner.peripheral.recv_slarw() {
+                        Ok(()) => match address.low_byte() {
+                            Some(low) => {
+                                self.inner.peripheral.send_write(low);
+                                *step = SlaStep::LowByte;
+                                false
+                            }
+                            None => {
+                                *res = Some(Ok(()));
+                                true
+                            }
+                        },
+                        Err(err) => {
+                            *res = Some(Err(err.into()));
+                            true
+                        }
+                    },
+                    SlaStep::ReadHeader => {
+                        *res = Some(self.inner.peripheral.recv_slarw().map_err(Into::into));
+                        true
+                    }
+                    SlaStep::LowByte => match self.inner.peripheral.recv_write() {
+                        Ok(()) if *direction == peripheral::Direction::Read => {
+                            // 10-bit reads switch direction with a repeated START.
+                            self.inner.peripheral.send_start();
+                            *step = SlaStep::Restart;
+                            false
+                        }
+                        Ok(()) => {
+                            *res = Some(Ok(()));
+                            true
+                        }
+                        Err(peripheral::Error::DataNack) => {
+                            *res = Some(Err(Error::AddressNack));
+                            true
+                        }
+                        Err(err) => {
+                            *res = Some(Err(err.into()));
+                            true
+                        }
+                    },
+                    SlaStep::Restart => match self.inner.peripheral.recv_start() {
+                        Ok(()) => {
+                            self.inner
+                                .peripheral
+                                .send_slarw(address.header(), peripheral::Direction::Read);
+                            *step = SlaStep::ReadHeader;
+                            false
+                        }
+                        Err(err) => {
+                            *res = Some(Err(err.into()));
+                            true
+                        }
+                    },
+                },
                 State::Write {
                     buf,
                     ref mut idx,
@@ -88,13 +185,14 @@ impl<TWI: self::peripheral::TwiOps<SDA, SCL>, SDA, SCL, CLOCK>
                         }
                     }
                     Err(err) => {
-                        *res = Some(Err(err));
+                        *res = Some(Err(err.into()));
                         true
                     }
                 },
                 State::Read {
                     buf,
                     ref mut idx,
+                    last,
                     ref mut res,
                 } => match self.inner.peripheral.recv_read() {
                     Ok(byte) => {
@@ -106,19 +204,112 @@ impl<TWI: self::peripheral::TwiOps<SDA, SCL>, SDA, SCL, CLOCK>
                             *res = Some(Ok(()));
                             true
                         } else {
-                            self.inner.peripheral.send_read(buf.len() - 1 == *idx);
+                            self.inner
+                                .peripheral
+                                .send_read(*last && buf.len() - 1 == *idx);
                             false
                         }
                     }
                     Err(err) => {
-                        *res = Some(Err(err));
-                        false
+                        *res = Some(Err(err.into()));
+                        true
                     }
                 },
                 State::Stop(ref mut res) => {
                     *res = Some(());
                     true
                 }
+                #[cfg(not(any(
+                    feature = "attiny84",
+                    feature = "attiny85",
+                    feature = "attiny167"
+                )))]
+                State::Listen(ref mut res) => match self.inner.peripheral.recv_addressed() {
+                    Ok(Some(request)) => {
+                        // The clock stays stretched until the task answers.
+                        *res = Some(Ok(request));
+                        true
+                    }
+                    Ok(None) => {
+                        self.inner.peripheral.send_slave_ack(true);
+                        false
+                    }
+                    Err(err) => {
+                        *res = Some(Err(err.into()));
+                        self.inner.peripheral.send_stop();
+                        true
+                    }
+                },
+                #[cfg(not(any(
+                    feature = "attiny84",
+                    feature = "attiny85",
+                    feature = "attiny167"
+                )))]
+                State::SlaveReceive {
+                    buf,
+                    ref mut idx,
+                    ref mut res,
+                } => match self.inner.peripheral.recv_slave_read() {
+                    Ok(Some((byte, ack))) => {
+                        let buf = unsafe { &mut *(*buf) };
+                        if let Some(x) = buf.get_mut(*idx) {
+                            *x = byte;
+                            *idx += 1;
+                        }
+
+                        if ack {
+                            self.inner
+                                .peripheral
+                                .send_slave_ack(buf.len().saturating_sub(*idx) > 1);
+                            false
+                        } else {
+                            *res = Some(Ok(*idx));
+                            self.inner.peripheral.release_slave(true);
+                            true
+                        }
+                    }
+                    Ok(None) => {
+                        *res = Some(Ok(*idx));
+                        self.inner.peripheral.release_slave(true);
+                        true
+                    }
+                    Err(err) => {
+                        *res = Some(Err(err.into()));
+                        self.inner.peripheral.send_stop();
+                        true
+                    }
+                },
+                #[cfg(not(any(
+                    feature = "attiny84",
+                    feature = "attiny85",
+                    feature = "attiny167"
+                )))]
+                State::SlaveTransmit {
+                    buf,
+                    ref mut idx,
+                    ref mut res,
+                } => match self.inner.peripheral.recv_slave_write() {
+                    Ok(true) => {
+                        let buf = unsafe { &*(*buf) };
+                        // Past the end of the buffer the master reads 0xff.
+                        let byte = buf.get(*idx).copied().unwrap_or(0xff);
+                        *idx = (*idx + 1).min(buf.len());
+                        self.inner
+                            .peripheral
+                            .send_slave_write(byte, *idx >= buf.len());
+                        false
+                    }
+                    Ok(false) => {
+                        *res = Some(Ok(*idx));
+                        self.inner.peripheral.release_slave(true);
+                        true
+                    }
+                    Err(err) => {
+                        *res = Some(Err(err.into()));
+                        self.inner.peripheral.send_stop();
+                        true
+                    }
+                },
             };
 
             if res {
@@ -179,7 +370,7 @@ impl<TWI: self::peripheral::TwiOps<SDA, SCL>, SDA, SCL, CLOCK>
     #[inline]
     pub fn write<'a, 'b>(
         &'a mut self,
-        addr: u8,
+        addr: Address,
         buf: &'b [u8],
     ) -> write::Write<'a, 'b, TWI, SDA, SCL, CLOCK> {
         write::Write::new(&mut self.inner, addr, buf)
@@ -188,16 +379,109 @@ impl<TWI: self::peripheral::TwiOps<SDA, SCL>, SDA, SCL, CLOCK>
     #[inline]
     pub fn read<'a, 'b>(
         &'a mut self,
-        addr: u8,
+        addr: Address,
         buf: &'b mut [u8],
     ) -> read::Read<'a, 'b, TWI, SDA, SCL, CLOCK> {
         read::Read::new(&mut self.inner, addr, buf)
     }
 
+    /// Bounds every following master operation by `timeout`, `None` waits forever. A timed out
+    /// operation clears the bus, see [`peripheral::TwiPeripheral::recover`], and fails with
+    /// [`Error::BusStuck`].
+    #[cfg(feature = "time")]
+    #[inline(always)]
+    pub fn set_timeout(&mut self, timeout: Option<crate::time::Duration>) {
+        self.inner.inner.timeout = timeout;
+    }
+
+    /// Waits for the bus and holds it for several reads and writes, each with its own START. The
+    /// STOP is sent when the [`transaction::Transaction`] is dropped.
     #[inline(always)]
     pub fn transaction(&mut self) -> transaction::GetTransaction<TWI, SDA, SCL, CLOCK> {
         transaction::GetTransaction::new(&mut self.inner)
     }
+
+    /// Runs `operations` on the device at `addr` with the semantics of embedded-hal: contiguous
+    /// operations of the same direction are merged without a START in between, a change of
+    /// direction sends a repeated START, a single STOP ends the transaction, also on error.
+    /// Unlike [`Self::transaction`] the whole bus access is described up front.
+    #[inline]
+    pub async fn execute(
+        &mut self,
+        addr: Address,
+        operations: &mut [embedded_hal::i2c::Operation<'_>],
+    ) -> Result<(), Error> {
+        i2c::transaction(&mut self.inner, addr, operations).await
+    }
+
+    /// Sets the address this interface answers to as a slave, `general_call` also answers to
+    /// address 0. The hardware only matches 7-bit addresses.
+    #[cfg(not(any(feature = "attiny84", feature = "attiny85", feature = "attiny167")))]
+    #[inline]
+    pub fn set_address(
+        &mut self,
+        address: Address,
+        general_call: bool,
+    ) -> Result<(), InvalidAddress> {
+        self.inner
+            .inner
+            .peripheral
+            .set_address(address.try_into()?, general_call);
+        Ok(())
+    }
+
+    /// Address bits set in `mask` are ignored, the interface answers to a range of addresses.
+    #[cfg(not(any(feature = "attiny84", feature = "attiny85", feature = "attiny167")))]
+    #[inline]
+    pub fn set_address_mask(&mut self, mask: u8) {
+        self.inner.inner.peripheral.set_address_mask(mask);
+    }
+
+    /// Waits for a master to address this interface.
+    ///
+    /// The clock is stretched from the moment the interface is addressed until the returned
+    /// [`slave::Request`] is answered, a dropped request is not acknowledged.
+    #[cfg(not(any(feature = "attiny84", feature = "attiny85", feature = "attiny167")))]
+    #[inline]
+    pub fn listen(&mut self) -> slave::Listen<TWI, SDA, SCL, CLOCK> {
+        slave::Listen::new(&mut self.inner.inner)
+    }
+}
+
+/// Bounds an operation by the timeout of the interface, must be pinned with the operation.
+pub(crate) struct Deadline {
+    #[cfg(feature = "time")]
+    sleep: Option<crate::time::Sleep>,
+}
+
+impl Deadline {
+    #[inline(always)]
+    #[allow(unused_variables)]
+    pub(crate) fn new<TWI: peripheral::TwiOps<SDA, SCL>, SDA, SCL, CLOCK>(
+        twi: &raw::TwoWireInterface<TWI, SDA, SCL, CLOCK>,
+    ) -> Self {
+        Self {
+            #[cfg(feature = "time")]
+            sleep: twi.inner.timeout.map(crate::time::sleep),
+        }
+    }
+
+    /// Whether the operation ran out of time.
+    #[cfg(feature = "time")]
+    #[inline]
+    pub(crate) fn poll_elapsed(self: Pin<&mut Self>, cx: &mut Context<'_>) -> bool {
+        let this = unsafe { Pin::get_unchecked_mut(self) };
+        match this.sleep {
+            Some(ref mut sleep) => unsafe { Pin::new_unchecked(sleep) }.poll(cx).is_ready(),
+            None => false,
+        }
+    }
+
+    #[cfg(not(feature = "time"))]
+    #[inline(always)]
+    pub(crate) fn poll_elapsed(self: Pin<&mut Self>, _: &mut Context<'_>) -> bool {
+        false
+    }
 }
 
 pub struct Wait<'a> {
@@ -222,7 +506,8 @@ impl<'a> Future for Wait<'a> {
         self: core::pin::Pin<&mut Self>,
         _: &mut core::task::Context<'_>,
     ) -> core::task::Poll<Self::Output> {
-        if *self.val {
+        // `set` stays true while a command is in flight, `run` clears it and wakes the executor.
+        if !*self.val {
             core::task::Poll::Ready(())
         } else {
             core::task::Poll::Pending
@@ -230,7 +515,19 @@ impl<'a> Future for Wait<'a> {
     }
 }
 
-#[cfg(feature = "atmega328p")]
+#[cfg(any(
+    feature = "atmega48p",
+    feature = "atmega168",
+    feature = "atmega328p",
+    feature = "atmega328pb",
+    feature = "attiny88",
+    feature = "atmega1280",
+    feature = "atmega2560",
+    feature = "atmega32u4",
+    feature = "attiny84",
+    feature = "attiny85",
+    feature = "attiny167",
+))]
 pub type TwoWireInterface1<CLOCK> = TwoWireInterface<
     self::peripheral::TwiPeripheral1Pac,
     port::Pin<port::mode::Input, self::peripheral::TwiPeripheral1Sda>,
@@ -238,10 +535,38 @@ pub type TwoWireInterface1<CLOCK> = TwoWireInterface<
     CLOCK,
 >;
 
-#[cfg(feature = "atmega328p")]
+#[cfg(any(
+    feature = "atmega48p",
+    feature = "atmega168",
+    feature = "atmega328p",
+    feature = "atmega328pb",
+    feature = "attiny88",
+    feature = "atmega1280",
+    feature = "atmega2560",
+    feature = "atmega32u4",
+    feature = "attiny84",
+    feature = "attiny85",
+    feature = "attiny167",
+))]
 pub type TwoWireInterfaceDriver1<CLOCK> = TwoWireInterfaceDriver<
     self::peripheral::TwiPeripheral1Pac,
     port::Pin<port::mode::Input, self::peripheral::TwiPeripheral1Sda>,
     port::Pin<port::mode::Input, self::peripheral::TwiPeripheral1Scl>,
     CLOCK,
 >;
+
+#[cfg(feature = "atmega328pb")]
+pub type TwoWireInterface2<CLOCK> = TwoWireInterface<
+    self::peripheral::TwiPeripheral2Pac,
+    port::Pin<port::mode::Input, self::peripheral::TwiPeripheral2Sda>,
+    port::Pin<port::mode::Input, self::peripheral::TwiPeripheral2Scl>,
+    CLOCK,
+>;
+
+#[cfg(feature = "atmega328pb")]
+pub type TwoWireInterfaceDriver2<CLOCK> = TwoWireInterfaceDriver<
+    self::peripheral::TwiPeripheral2Pac,
+    port::Pin<port::mode::Input, self::peripheral::TwiPeripheral2Sda>,
+    port::Pin<port::mode::Input, self::peripheral::TwiPeripheral2Scl>,
+    CLOCK,
+>;
@@ -31,14 +31,58 @@ pub trait TwiOps<SDA, SCL> {
 
     fn recv_read(&mut self) -> Result<u8, Error>;
 
+    /// Masks the interrupt once an operation is done, a pending event keeps the clock stretched
+    /// until the next command.
     fn disable(&mut self);
+
+    /// Turns the TWI off, handing SDA and SCL back to the port, or on again.
+    fn set_enabled(&mut self, enabled: bool);
+
+    /// Sets the own slave address (TWAR), `general_call` also answers to address 0.
+    #[cfg(not(any(feature = "attiny84", feature = "attiny85", feature = "attiny167")))]
+    fn set_address(&mut self, address: u8, general_call: bool);
+
+    /// Address bits set in `mask` are ignored when matching the own address (TWAMR).
+    #[cfg(not(any(feature = "attiny84", feature = "attiny85", feature = "attiny167")))]
+    fn set_address_mask(&mut self, mask: u8);
+
+    /// Acknowledges the own address, without clearing a pending event.
+    #[cfg(not(any(feature = "attiny84", feature = "attiny85", feature = "attiny167")))]
+    fn listen(&mut self);
+
+    /// Leaves the current slave transfer, `listen` keeps acknowledging the own address.
+    #[cfg(not(any(feature = "attiny84", feature = "attiny85", feature = "attiny167")))]
+    fn release_slave(&mut self, listen: bool);
+
+    /// Accepts the next byte from the master, `ack` is false for the last one.
+    #[cfg(not(any(feature = "attiny84", feature = "attiny85", feature = "attiny167")))]
+    fn send_slave_ack(&mut self, ack: bool);
+
+    /// Queues a byte for the master, `last` tells the master no more data follows.
+    #[cfg(not(any(feature = "attiny84", feature = "attiny85", feature = "attiny167")))]
+    fn send_slave_write(&mut self, byte: u8, last: bool);
+
+    /// The master direction and whether it was a general call, `None` for unrelated events.
+    #[cfg(not(any(feature = "attiny84", feature = "attiny85", feature = "attiny167")))]
+    fn recv_addressed(&mut self) -> Result<Option<(Direction, bool)>, Error>;
+
+    /// The received byte and whether it was acknowledged, `None` on STOP or repeated START.
+    #[cfg(not(any(feature = "attiny84", feature = "attiny85", feature = "attiny167")))]
+    fn recv_slave_read(&mut self) -> Result<Option<(u8, bool)>, Error>;
+
+    /// Whether the master wants another byte.
+    #[cfg(not(any(feature = "attiny84", feature = "attiny85", feature = "attiny167")))]
+    fn recv_slave_write(&mut self) -> Result<bool, Error>;
 }
 
+// `sei` and `impl_twi!` are unused on the chips with a USI only.
+#[allow(dead_code)]
 #[inline(always)]
 fn sei() {
     unsafe { ::core::arch::asm!("sei") };
 }
 
+#[allow(unused_macros)]
 macro_rules! impl_twi {
     (:: $krate_head:ident $(:: $krate_rest:ident)*, $TWI:ident, $SDA:ident, $SCL:ident) => {
         impl_twi!(@def $TWI, $SDA, $SCL, :: $krate_head $(:: $krate_rest)*);
@@ -48,7 +92,7 @@ macro_rules! impl_twi {
     };
     (@def $TWI:ident, $SDA:ident, $SCL:ident, $($krate:tt)+) => {
         impl
-            $crate::twi::peripheral::TwiOps<
+            $crate::twi2::peripheral::TwiOps<
                 $($krate)* ::port::Pin<$($krate)* ::port::mode::Input, $($krate)* ::port::$SDA>,
                 $($krate)* ::port::Pin<$($krate)* ::port::mode::Input, $($krate)* ::port::$SCL>,
             > for $($krate)* ::pac::$TWI
@@ -80,8 +124,8 @@ macro_rules! impl_twi {
             }
 
             #[inline(always)]
-            fn send_slarw(&mut self, address: u8, direction: $crate::twi::peripheral::Direction) {
-                let dirbit = if direction == $crate::twi::peripheral::Direction::Read { 1 } else { 0 };
+            fn send_slarw(&mut self, address: u8, direction: $crate::twi2::peripheral::Direction) {
+                let dirbit = if direction == $crate::twi2::peripheral::Direction::Read { 1 } else { 0 };
                 let rawaddr = (address << 1) | dirbit;
                 self.twdr.write(|w| unsafe { w.bits(rawaddr) });
                 sei();
@@ -141,60 +185,168 @@ macro_rules! impl_twi {
 
             #[allow(unreachable_patterns)]
             #[inline(always)]
-            fn recv_start(&mut self) -> Result<(), $crate::twi::peripheral::Error> {
+            fn recv_start(&mut self) -> Result<(), $crate::twi2::peripheral::Error> {
                 match self.twsr.read().tws().bits() {
                     $crate::reexports::avr_hal_generic::i2c::twi_status::TW_START | $crate::reexports::avr_hal_generic::i2c::twi_status::TW_REP_START => Ok(()),
                     $crate::reexports::avr_hal_generic::i2c::twi_status::TW_MT_ARB_LOST | $crate::reexports::avr_hal_generic::i2c::twi_status::TW_MR_ARB_LOST => {
-                        Err($crate::twi::peripheral::Error::ArbitrationLost)
+                        Err($crate::twi2::peripheral::Error::ArbitrationLost)
                     }
-                    $crate::reexports::avr_hal_generic::i2c::twi_status::TW_BUS_ERROR => Err($crate::twi::peripheral::Error::BusError),
-                    _ => Err($crate::twi::peripheral::Error::Unknown),
+                    $crate::reexports::avr_hal_generic::i2c::twi_status::TW_BUS_ERROR => Err($crate::twi2::peripheral::Error::BusError),
+                    _ => Err($crate::twi2::peripheral::Error::Unknown),
                 }
             }
 
             #[allow(unreachable_patterns)]
             #[inline(always)]
-            fn recv_slarw(&mut self) -> Result<(), $crate::twi::peripheral::Error> {
+            fn recv_slarw(&mut self) -> Result<(), $crate::twi2::peripheral::Error> {
                 match self.twsr.read().tws().bits() {
                     $crate::reexports::avr_hal_generic::i2c::twi_status::TW_MT_SLA_ACK | $crate::reexports::avr_hal_generic::i2c::twi_status::TW_MR_SLA_ACK => Ok(()),
                     $crate::reexports::avr_hal_generic::i2c::twi_status::TW_MT_SLA_NACK | $crate::reexports::avr_hal_generic::i2c::twi_status::TW_MR_SLA_NACK => {
-                        Err($crate::twi::peripheral::Error::AddressNack)
+                        Err($crate::twi2::peripheral::Error::AddressNack)
                     }
                     $crate::reexports::avr_hal_generic::i2c::twi_status::TW_MT_ARB_LOST | $crate::reexports::avr_hal_generic::i2c::twi_status::TW_MR_ARB_LOST => {
-                        Err($crate::twi::peripheral::Error::ArbitrationLost)
+                        Err($crate::twi2::peripheral::Error::ArbitrationLost)
                     }
-                    $crate::reexports::avr_hal_generic::i2c::twi_status::TW_BUS_ERROR => Err($crate::twi::peripheral::Error::BusError),
-                    _ => Err($crate::twi::peripheral::Error::Unknown),
+                    $crate::reexports::avr_hal_generic::i2c::twi_status::TW_BUS_ERROR => Err($crate::twi2::peripheral::Error::BusError),
+                    _ => Err($crate::twi2::peripheral::Error::Unknown),
                 }
             }
 
             #[inline(always)]
-            fn recv_write(&mut self) -> Result<(), $crate::twi::peripheral::Error> {
+            fn recv_write(&mut self) -> Result<(), $crate::twi2::peripheral::Error> {
                 match self.twsr.read().tws().bits() {
                     $crate::reexports::avr_hal_generic::i2c::twi_status::TW_MT_DATA_ACK => Ok(()),
-                    $crate::reexports::avr_hal_generic::i2c::twi_status::TW_MT_DATA_NACK => Err($crate::twi::peripheral::Error::DataNack),
-                    $crate::reexports::avr_hal_generic::i2c::twi_status::TW_MT_ARB_LOST => Err($crate::twi::peripheral::Error::ArbitrationLost),
-                    $crate::reexports::avr_hal_generic::i2c::twi_status::TW_BUS_ERROR => Err($crate::twi::peripheral::Error::BusError),
-                    _ => Err($crate::twi::peripheral::Error::Unknown),
+                    $crate::reexports::avr_hal_generic::i2c::twi_status::TW_MT_DATA_NACK => Err($crate::twi2::peripheral::Error::DataNack),
+                    $crate::reexports::avr_hal_generic::i2c::twi_status::TW_MT_ARB_LOST => Err($crate::twi2::peripheral::Error::ArbitrationLost),
+                    $crate::reexports::avr_hal_generic::i2c::twi_status::TW_BUS_ERROR => Err($crate::twi2::peripheral::Error::BusError),
+                    _ => Err($crate::twi2::peripheral::Error::Unknown),
                 }
             }
 
             #[inline(always)]
-            fn recv_read(&mut self) -> Result<u8, $crate::twi::peripheral::Error> {
+            fn recv_read(&mut self) -> Result<u8, $crate::twi2::peripheral::Error> {
                 match self.twsr.read().tws().bits() {
                     $crate::reexports::avr_hal_generic::i2c::twi_status::TW_MR_DATA_ACK | $crate::reexports::avr_hal_generic::i2c::twi_status::TW_MR_DATA_NACK => {
                         Ok(self.twdr.read().bits())
                     }
-                    $crate::reexports::avr_hal_generic::i2c::twi_status::TW_MR_ARB_LOST => Err($crate::twi::peripheral::Error::ArbitrationLost),
-                    $crate::reexports::avr_hal_generic::i2c::twi_status::TW_BUS_ERROR => Err($crate::twi::peripheral::Error::BusError),
-                    _ => Err($crate::twi::peripheral::Error::Unknown),
+                    $crate::reexports::avr_hal_generic::i2c::twi_status::TW_MR_ARB_LOST => Err($crate::twi2::peripheral::Error::ArbitrationLost),
+                    $crate::reexports::avr_hal_generic::i2c::twi_status::TW_BUS_ERROR => Err($crate::twi2::peripheral::Error::BusError),
+                    _ => Err($crate::twi2::peripheral::Error::Unknown),
                 }
             }
 
             #[inline(always)]
             fn disable(&mut self) {
+                // TWINT is cleared by writing a one, written back as zero the flag stays set and so
+                // does the stretched clock.
+                self.twcr.modify(|r, w| unsafe {
+                    w.bits(r.bits()).twint().clear_bit().twie().clear_bit()
+                });
+            }
+
+            #[inline(always)]
+            fn set_enabled(&mut self, enabled: bool) {
+                self.twcr.write(|w| w.twen().bit(enabled));
+            }
+
+            #[inline(always)]
+            fn set_address(&mut self, address: u8, general_call: bool) {
+                self.twar.write(|w| unsafe { w.bits((address << 1) | general_call as u8) });
+            }
+
+            #[inline(always)]
+            fn set_address_mask(&mut self, mask: u8) {
+                self.twamr.write(|w| unsafe { w.bits(mask << 1) });
+            }
+
+            #[inline(always)]
+            fn listen(&mut self) {
+                sei();
+                self.twcr
+                    .write(|w| w.twen().set_bit().twea().set_bit().twie().set_bit());
+            }
+
+            #[inline(always)]
+            fn release_slave(&mut self, listen: bool) {
+                self.twdr.write(|w| unsafe { w.bits(0xff) });
                 self.twcr
-                    .modify(|r, w| unsafe { w.bits(r.bits()).twie().clear_bit() });
+                    .write(|w| w.twen().set_bit().twint().set_bit().twea().bit(listen));
+            }
+
+            #[inline(always)]
+            fn send_slave_ack(&mut self, ack: bool) {
+                sei();
+                self.twcr.write(|w| {
+                    w.twint()
+                        .set_bit()
+                        .twen()
+                        .set_bit()
+                        .twea()
+                        .bit(ack)
+                        .twie()
+                        .set_bit()
+                });
+            }
+
+            #[inline(always)]
+            fn send_slave_write(&mut self, byte: u8, last: bool) {
+                self.twdr.write(|w| unsafe { w.bits(byte) });
+                sei();
+                self.twcr.write(|w| {
+                    w.twint()
+                        .set_bit()
+                        .twen()
+                        .set_bit()
+                        .twea()
+                        .bit(!last)
+                        .twie()
+                        .set_bit()
+                });
+            }
+
+            #[allow(unreachable_patterns)]
+            #[inline(always)]
+            fn recv_addressed(&mut self) -> Result<Option<($crate::twi2::peripheral::Direction, bool)>, $crate::twi2::peripheral::Error> {
+                match self.twsr.read().tws().bits() {
+                    $crate::reexports::avr_hal_generic::i2c::twi_status::TW_SR_SLA_ACK | $crate::reexports::avr_hal_generic::i2c::twi_status::TW_SR_ARB_LOST_SLA_ACK => {
+                        Ok(Some(($crate::twi2::peripheral::Direction::Write, false)))
+                    }
+                    $crate::reexports::avr_hal_generic::i2c::twi_status::TW_SR_GCALL_ACK | $crate::reexports::avr_hal_generic::i2c::twi_status::TW_SR_ARB_LOST_GCALL_ACK => {
+                        Ok(Some(($crate::twi2::peripheral::Direction::Write, true)))
+                    }
+                    $crate::reexports::avr_hal_generic::i2c::twi_status::TW_ST_SLA_ACK | $crate::reexports::avr_hal_generic::i2c::twi_status::TW_ST_ARB_LOST_SLA_ACK => {
+                        Ok(Some(($crate::twi2::peripheral::Direction::Read, false)))
+                    }
+                    $crate::reexports::avr_hal_generic::i2c::twi_status::TW_BUS_ERROR => Err($crate::twi2::peripheral::Error::BusError),
+                    _ => Ok(None),
+                }
+            }
+
+            #[allow(unreachable_patterns)]
+            #[inline(always)]
+            fn recv_slave_read(&mut self) -> Result<Option<(u8, bool)>, $crate::twi2::peripheral::Error> {
+                match self.twsr.read().tws().bits() {
+                    $crate::reexports::avr_hal_generic::i2c::twi_status::TW_SR_DATA_ACK | $crate::reexports::avr_hal_generic::i2c::twi_status::TW_SR_GCALL_DATA_ACK => {
+                        Ok(Some((self.twdr.read().bits(), true)))
+                    }
+                    $crate::reexports::avr_hal_generic::i2c::twi_status::TW_SR_DATA_NACK | $crate::reexports::avr_hal_generic::i2c::twi_status::TW_SR_GCALL_DATA_NACK => {
+                        Ok(Some((self.twdr.read().bits(), false)))
+                    }
+                    $crate::reexports::avr_hal_generic::i2c::twi_status::TW_SR_STOP => Ok(None),
+                    $crate::reexports::avr_hal_generic::i2c::twi_status::TW_BUS_ERROR => Err($crate::twi2::peripheral::Error::BusError),
+                    _ => Err($crate::twi2::peripheral::Error::Unknown),
+                }
+            }
+
+            #[allow(unreachable_patterns)]
+            #[inline(always)]
+            fn recv_slave_write(&mut self) -> Result<bool, $crate::twi2::peripheral::Error> {
+                match self.twsr.read().tws().bits() {
+                    $crate::reexports::avr_hal_generic::i2c::twi_status::TW_ST_DATA_ACK => Ok(true),
+                    $crate::reexports::avr_hal_generic::i2c::twi_status::TW_ST_DATA_NACK | $crate::reexports::avr_hal_generic::i2c::twi_status::TW_ST_LAST_DATA => Ok(false),
+                    $crate::reexports::avr_hal_generic::i2c::twi_status::TW_BUS_ERROR => Err($crate::twi2::peripheral::Error::BusError),
+                    _ => Err($crate::twi2::peripheral::Error::Unknown),
+                }
             }
         }
     };
@@ -207,6 +359,8 @@ pub struct TwiPeripheral<TWI: TwiOps<SDA, SCL>, SDA, SCL, CLOCK> {
     sda: SDA,
     #[allow(dead_code)]
     scl: SCL,
+    recover: fn(SDA, SCL, bool) -> (SDA, SCL, bool),
+    pull_up: bool,
     _clock: PhantomData<CLOCK>,
 }
 
@@ -233,6 +387,8 @@ where
             p,
             sda: sda.forget_imode(),
             scl: scl.forget_imode(),
+            recover: clear_bus::<SDAPIN, SCLPIN, CLOCK>,
+            pull_up: true,
             _clock: PhantomData,
         };
         twi.p.setup::<CLOCK>(speed);
@@ -249,6 +405,8 @@ where
             p,
             sda: sda.forget_imode(),
             scl: scl.forget_imode(),
+            recover: clear_bus::<SDAPIN, SCLPIN, CLOCK>,
+            pull_up: false,
             _clock: PhantomData,
         };
         twi.p.setup::<CLOCK>(speed);
@@ -256,6 +414,108 @@ where
     }
 }
 
+impl<TWI: TwiOps<SDA, SCL>, SDA, SCL, CLOCK> TwiPeripheral<TWI, SDA, SCL, CLOCK> {
+    /// Frees a bus held by a slave: the TWI is disabled, SCL is pulsed up to 9 times until the
+    /// slave releases SDA, a STOP is sent by hand and the TWI is enabled again. Returns whether
+    /// both lines are high afterwards.
+    pub fn recover(&mut self) -> bool {
+        self.p.set_enabled(false);
+        // The pins are moved out for the sequence and back in right after, nothing in between
+        // can panic.
+        let free = unsafe {
+            let (sda, scl, free) = (self.recover)(
+                core::ptr::read(&self.sda),
+                core::ptr::read(&self.scl),
+                self.pull_up,
+            );
+            core::ptr::write(&mut self.sda, sda);
+            core::ptr::write(&mut self.scl, scl);
+            free
+        };
+        self.p.set_enabled(true);
+        free
+    }
+}
+
+/// Half a clock period at 100kHz or slower, a loop iteration takes at least 4 cycles.
+#[inline(always)]
+fn half_bit<CLOCK: Clock>() {
+    for _ in 0..CLOCK::FREQ / 800_000 {
+        unsafe { ::core::arch::asm!("nop") };
+    }
+}
+
+/// Half periods a slave may stretch the clock before the bus is given up.
+pub(crate) const STRETCH_LIMIT: u16 = 1000;
+
+/// Releases a line, to the internal pull-up or to the external one.
+#[inline(always)]
+fn release<MODE: port::mode::Io, PIN: port::PinOps>(
+    pin: port::Pin<MODE, PIN>,
+    pull_up: bool,
+) -> port::Pin<port::mode::Input, PIN> {
+    if pull_up {
+        pin.into_pull_up_input().forget_imode()
+    } else {
+        pin.into_floating_input().forget_imode()
+    }
+}
+
+/// Waits for a released line to go high.
+fn wait_high<PIN: port::PinOps, CLOCK: Clock>(pin: &port::Pin<port::mode::Input, PIN>) -> bool {
+    for _ in 0..STRETCH_LIMIT {
+        if pin.is_high() {
+            return true;
+        }
+        half_bit::<CLOCK>();
+    }
+    false
+}
+
+/// Bit-bangs the bus clear sequence, the TWI must be disabled so the port drives the pins.
+#[allow(clippy::type_complexity)]
+fn clear_bus<SDAPIN: port::PinOps, SCLPIN: port::PinOps, CLOCK: Clock>(
+    sda: port::Pin<port::mode::Input, SDAPIN>,
+    scl: port::Pin<port::mode::Input, SCLPIN>,
+    pull_up: bool,
+) -> (
+    port::Pin<port::mode::Input, SDAPIN>,
+    port::Pin<port::mode::Input, SCLPIN>,
+    bool,
+) {
+    // The port may still hold what the peripheral left in it, `is_high` must read the bus.
+    let sda = release(sda, pull_up);
+    let mut scl = release(scl, pull_up);
+    half_bit::<CLOCK>();
+
+    for _ in 0..9 {
+        if sda.is_high() {
+            break;
+        }
+        let low = scl.into_opendrain();
+        half_bit::<CLOCK>();
+        scl = release(low, pull_up);
+        // A slave holding SCL low can't be cleared from here.
+        if !wait_high::<_, CLOCK>(&scl) {
+            return (sda, scl, false);
+        }
+        half_bit::<CLOCK>();
+    }
+
+    // STOP: SDA rises while SCL is high.
+    let scl = scl.into_opendrain();
+    let sda = sda.into_opendrain();
+    half_bit::<CLOCK>();
+    let scl = release(scl, pull_up);
+    let stretched = !wait_high::<_, CLOCK>(&scl);
+    half_bit::<CLOCK>();
+    let sda = release(sda, pull_up);
+    half_bit::<CLOCK>();
+
+    let free = !stretched && sda.is_high() && scl.is_high();
+    (sda, scl, free)
+}
+
 impl<TWI: TwiOps<SDA, SCL>, SDA, SCL, CLOCK> const Deref for TwiPeripheral<TWI, SDA, SCL, CLOCK> {
     type Target = TWI;
 
@@ -310,19 +570,91 @@ impl<TWI: TwiOps<SDA, SCL>, SDA, SCL, CLOCK> const AsMut<TWI>
     }
 }
 
-#[cfg(feature = "atmega328p")]
+#[cfg(any(
+    feature = "atmega48p",
+    feature = "atmega168",
+    feature = "atmega328p",
+    feature = "attiny88",
+))]
 impl_twi!(crate::hal, TWI, PC4, PC5);
 
-#[cfg(feature = "atmega328p")]
+#[cfg(any(feature = "atmega1280", feature = "atmega2560", feature = "atmega32u4"))]
+impl_twi!(crate::hal, TWI, PD1, PD0);
+
+// TWI0 and TWI1 share the register layout.
+#[cfg(feature = "atmega328pb")]
+impl_twi!(crate::hal, TWI0, PC4, PC5);
+
+#[cfg(feature = "atmega328pb")]
+impl_twi!(crate::hal, TWI1, PE0, PE1);
+
+#[cfg(any(
+    feature = "atmega48p",
+    feature = "atmega168",
+    feature = "atmega328p",
+    feature = "attiny88",
+    feature = "atmega1280",
+    feature = "atmega2560",
+    feature = "atmega32u4",
+))]
 pub type TwiPeripheral1Pac = crate::hal::pac::TWI;
 
-#[cfg(feature = "atmega328p")]
+#[cfg(feature = "atmega328pb")]
+pub type TwiPeripheral1Pac = crate::hal::pac::TWI0;
+
+/// The USI in two-wire mode, see the `usi` module.
+#[cfg(any(feature = "attiny84", feature = "attiny85", feature = "attiny167"))]
+pub type TwiPeripheral1Pac = crate::hal::pac::USI;
+
+#[cfg(any(
+    feature = "atmega48p",
+    feature = "atmega168",
+    feature = "atmega328p",
+    feature = "atmega328pb",
+    feature = "attiny88",
+))]
 pub type TwiPeripheral1Sda = crate::hal::port::PC4;
 
-#[cfg(feature = "atmega328p")]
+#[cfg(any(
+    feature = "atmega48p",
+    feature = "atmega168",
+    feature = "atmega328p",
+    feature = "atmega328pb",
+    feature = "attiny88",
+))]
 pub type TwiPeripheral1Scl = crate::hal::port::PC5;
 
-#[cfg(feature = "atmega328p")]
+#[cfg(any(feature = "atmega1280", feature = "atmega2560", feature = "atmega32u4"))]
+pub type TwiPeripheral1Sda = crate::hal::port::PD1;
+
+#[cfg(any(feature = "atmega1280", feature = "atmega2560", feature = "atmega32u4"))]
+pub type TwiPeripheral1Scl = crate::hal::port::PD0;
+
+#[cfg(any(feature = "attiny85", feature = "attiny167"))]
+pub type TwiPeripheral1Sda = crate::hal::port::PB0;
+
+#[cfg(any(feature = "attiny85", feature = "attiny167"))]
+pub type TwiPeripheral1Scl = crate::hal::port::PB2;
+
+#[cfg(feature = "attiny84")]
+pub type TwiPeripheral1Sda = crate::hal::port::PA6;
+
+#[cfg(feature = "attiny84")]
+pub type TwiPeripheral1Scl = crate::hal::port::PA4;
+
+#[cfg(any(
+    feature = "atmega48p",
+    feature = "atmega168",
+    feature = "atmega328p",
+    feature = "atmega328pb",
+    feature = "attiny88",
+    feature = "atmega1280",
+    feature = "atmega2560",
+    feature = "atmega32u4",
+    feature = "attiny84",
+    feature = "attiny85",
+    feature = "attiny167",
+))]
 pub type TwiPeripheral1<CLOCK> = TwiPeripheral<
     TwiPeripheral1Pac,
     port::Pin<port::mode::Input, TwiPeripheral1Sda>,
@@ -330,11 +662,35 @@ pub type TwiPeripheral1<CLOCK> = TwiPeripheral<
     CLOCK,
 >;
 
-#[cfg(feature = "atmega328p")]
+/// TWI1 of the atmega328pb.
+#[cfg(feature = "atmega328pb")]
+pub type TwiPeripheral2Pac = crate::hal::pac::TWI1;
+
+#[cfg(feature = "atmega328pb")]
+pub type TwiPeripheral2Sda = crate::hal::port::PE0;
+
+#[cfg(feature = "atmega328pb")]
+pub type TwiPeripheral2Scl = crate::hal::port::PE1;
+
+#[cfg(feature = "atmega328pb")]
+pub type TwiPeripheral2<CLOCK> = TwiPeripheral<
+    TwiPeripheral2Pac,
+    port::Pin<port::mode::Input, TwiPeripheral2Sda>,
+    port::Pin<port::mode::Input, TwiPeripheral2Scl>,
+    CLOCK,
+>;
+
+// `twi!` builds the peripheral of the `twi` module, these build the one of `twi2`.
+#[cfg(any(
+    feature = "atmega48p",
+    feature = "atmega168",
+    feature = "atmega328p",
+    feature = "attiny88",
+))]
 #[macro_export]
-macro_rules! twi {
+macro_rules! two_wire {
     ($peripherals:ident, $pins:ident, $clock:ty, $speed:expr) => {{
-        $crate::twi::peripheral::TwiPeripheral1::<$clock>::new(
+        $crate::twi2::peripheral::TwiPeripheral1::<$clock>::new(
             $peripherals.TWI,
             $pins.pc4.into_pull_up_input(),
             $pins.pc5.into_pull_up_input(),
@@ -343,11 +699,16 @@ macro_rules! twi {
     }};
 }
 
-#[cfg(feature = "atmega328p")]
+#[cfg(any(
+    feature = "atmega48p",
+    feature = "atmega168",
+    feature = "atmega328p",
+    feature = "attiny88",
+))]
 #[macro_export]
-macro_rules! twi_external_pullup {
+macro_rules! two_wire_external_pullup {
     ($peripherals:ident, $pins:ident, $clock:ty, $speed:expr) => {{
-        $crate::twi::peripheral::TwiPeripheral1::<$clock>::with_external_pullup(
+        $crate::twi2::peripheral::TwiPeripheral1::<$clock>::with_external_pullup(
             $peripherals.TWI,
             $pins.pc4.into_floating_input(),
             $pins.pc5.into_floating_input(),
@@ -355,3 +716,133 @@ macro_rules! twi_external_pullup {
         )
     }};
 }
+
+#[cfg(any(feature = "atmega1280", feature = "atmega2560", feature = "atmega32u4"))]
+#[macro_export]
+macro_rules! two_wire {
+    ($peripherals:ident, $pins:ident, $clock:ty, $speed:expr) => {{
+        $crate::twi2::peripheral::TwiPeripheral1::<$clock>::new(
+            $peripherals.TWI,
+            $pins.pd1.into_pull_up_input(),
+            $pins.pd0.into_pull_up_input(),
+            $speed,
+        )
+    }};
+}
+
+#[cfg(any(feature = "atmega1280", feature = "atmega2560", feature = "atmega32u4"))]
+#[macro_export]
+macro_rules! two_wire_external_pullup {
+    ($peripherals:ident, $pins:ident, $clock:ty, $speed:expr) => {{
+        $crate::twi2::peripheral::TwiPeripheral1::<$clock>::with_external_pullup(
+            $peripherals.TWI,
+            $pins.pd1.into_floating_input(),
+            $pins.pd0.into_floating_input(),
+            $speed,
+        )
+    }};
+}
+
+#[cfg(feature = "atmega328pb")]
+#[macro_export]
+macro_rules! two_wire {
+    ($peripherals:ident, $pins:ident, $clock:ty, $speed:expr) => {{
+        $crate::twi2::peripheral::TwiPeripheral1::<$clock>::new(
+            $peripherals.TWI0,
+            $pins.pc4.into_pull_up_input(),
+            $pins.pc5.into_pull_up_input(),
+            $speed,
+        )
+    }};
+}
+
+#[cfg(feature = "atmega328pb")]
+#[macro_export]
+macro_rules! two_wire_external_pullup {
+    ($peripherals:ident, $pins:ident, $clock:ty, $speed:expr) => {{
+        $crate::twi2::peripheral::TwiPeripheral1::<$clock>::with_external_pullup(
+            $peripherals.TWI0,
+            $pins.pc4.into_floating_input(),
+            $pins.pc5.into_floating_input(),
+            $speed,
+        )
+    }};
+}
+
+#[cfg(feature = "atmega328pb")]
+#[macro_export]
+macro_rules! two_wire2 {
+    ($peripherals:ident, $pins:ident, $clock:ty, $speed:expr) => {{
+        $crate::twi2::peripheral::TwiPeripheral2::<$clock>::new(
+            $peripherals.TWI1,
+            $pins.pe0.into_pull_up_input(),
+            $pins.pe1.into_pull_up_input(),
+            $speed,
+        )
+    }};
+}
+
+#[cfg(feature = "atmega328pb")]
+#[macro_export]
+macro_rules! two_wire2_external_pullup {
+    ($peripherals:ident, $pins:ident, $clock:ty, $speed:expr) => {{
+        $crate::twi2::peripheral::TwiPeripheral2::<$clock>::with_external_pullup(
+            $peripherals.TWI1,
+            $pins.pe0.into_floating_input(),
+            $pins.pe1.into_floating_input(),
+            $speed,
+        )
+    }};
+}
+
+#[cfg(any(feature = "attiny85", feature = "attiny167"))]
+#[macro_export]
+macro_rules! two_wire {
+    ($peripherals:ident, $pins:ident, $clock:ty, $speed:expr) => {{
+        $crate::twi2::peripheral::TwiPeripheral1::<$clock>::new(
+            $peripherals.USI,
+            $pins.pb0.into_pull_up_input(),
+            $pins.pb2.into_pull_up_input(),
+            $speed,
+        )
+    }};
+}
+
+#[cfg(any(feature = "attiny85", feature = "attiny167"))]
+#[macro_export]
+macro_rules! two_wire_external_pullup {
+    ($peripherals:ident, $pins:ident, $clock:ty, $speed:expr) => {{
+        $crate::twi2::peripheral::TwiPeripheral1::<$clock>::with_external_pullup(
+            $peripherals.USI,
+            $pins.pb0.into_floating_input(),
+            $pins.pb2.into_floating_input(),
+            $speed,
+        )
+    }};
+}
+
+#[cfg(feature = "attiny84")]
+#[macro_export]
+macro_rules! two_wire {
+    ($peripherals:ident, $pins:ident, $clock:ty, $speed:expr) => {{
+        $crate::twi2::peripheral::TwiPeripheral1::<$clock>::new(
+            $peripherals.USI,
+            $pins.pa6.into_pull_up_input(),
+            $pins.pa4.into_pull_up_input(),
+            $speed,
+        )
+    }};
+}
+
+#[cfg(feature = "attiny84")]
+#[macro_export]
+macro_rules! two_wire_external_pullup {
+    ($peripherals:ident, $pins:ident, $clock:ty, $speed:expr) => {{
+        $crate::twi2::peripheral::TwiPeripheral1::<$clock>::with_external_pullup(
+            $peripherals.USI,
+            $pins.pa6.into_floating_input(),
+            $pins.pa4.into_floating_input(),
+            $speed,
+        )
+    }};
+}
@@ -1,5 +1,7 @@
 use core::{future::Future, marker::PhantomData, task::Poll};
 
+use avr_device::interrupt;
+
 use crate::slab::{Slab, SlabBox, Slabbed};
 
 pub(crate) struct TwoWireInterface<TWI: super::peripheral::TwiOps<SDA, SCL>, SDA, SCL, CLOCK> {
@@ -34,7 +36,11 @@ impl<TWI: super::peripheral::TwiOps<SDA, SCL>, SDA, SCL, CLOCK>
     }
 
     #[inline]
-    pub fn sla_rw(&mut self, addr: u8, direction: super::peripheral::Direction) -> SlaRw {
+    pub fn sla_rw(
+        &mut self,
+        addr: super::Address,
+        direction: super::peripheral::Direction,
+    ) -> SlaRw {
         SlaRw::new(self.inner.as_mut(), addr, direction)
     }
 
@@ -43,9 +49,11 @@ impl<TWI: super::peripheral::TwiOps<SDA, SCL>, SDA, SCL, CLOCK>
         Write::new(self.inner.as_mut(), buf)
     }
 
+    /// Reads into `buf`, `last` if no read follows without a START so the last byte is not
+    /// acknowledged.
     #[inline]
-    pub fn read<'a, 'b>(&'a mut self, buf: &'b mut [u8]) -> Read<'a, 'b> {
-        Read::new(self.inner.as_mut(), buf)
+    pub fn read<'a, 'b>(&'a mut self, buf: &'b mut [u8], last: bool) -> Read<'a, 'b> {
+        Read::new(self.inner.as_mut(), buf, last)
     }
 
     #[inline]
@@ -53,10 +61,22 @@ impl<TWI: super::peripheral::TwiOps<SDA, SCL>, SDA, SCL, CLOCK>
         Stop::new(self.inner.as_mut())
     }
 
+    /// Abandons the current operation and clears the bus, the ISR leaves it alone from now on.
+    /// Returns the error of the timed out operation.
+    #[inline]
+    pub fn recover(&mut self) -> super::Error {
+        interrupt::free(|_cs| {
+            self.inner.set = false;
+            self.inner.peripheral.recover();
+        });
+        super::Error::BusStuck
+    }
+
     #[inline]
     pub(crate) fn stop_unbound(&mut self) {
-        self.inner.command.write(super::State::Stop(None));
-        self.inner.set = true;
+        // No interrupt follows a STOP, it is done as soon as it is sent.
+        self.inner.command.write(super::State::Stop(Some(())));
+        self.inner.set = false;
         self.inner.peripheral.send_stop();
     }
 }
@@ -73,7 +93,7 @@ impl<'a> Start<'a> {
         inner.set = true;
         inner.peripheral.send_start();
         Self {
-            state: unsafe { &mut *(inner.command.as_mut_ptr() as *mut super::State) },
+            state: unsafe { &mut *inner.command.as_mut_ptr() },
         }
     }
 }
@@ -104,14 +124,27 @@ pub struct SlaRw<'a> {
 impl<'a> SlaRw<'a> {
     pub fn new<TWI: super::peripheral::TwiOps<SDA, SCL>, SDA, SCL, CLOCK>(
         inner: &'a mut super::TwiSlab<TWI, SDA, SCL, CLOCK>,
-        addr: u8,
+        addr: super::Address,
         direction: super::peripheral::Direction,
     ) -> Self {
-        inner.command.write(super::State::SlaRw(None));
+        inner.command.write(super::State::SlaRw {
+            address: addr,
+            direction,
+            step: super::SlaStep::Header,
+            res: None,
+        });
         inner.set = true;
-        inner.peripheral.send_slarw(addr, direction);
+        // A 10-bit read is addressed for writing first.
+        inner.peripheral.send_slarw(
+            addr.header(),
+            if addr.is_ten_bit() {
+                super::peripheral::Direction::Write
+            } else {
+                direction
+            },
+        );
         Self {
-            state: unsafe { &mut *(inner.command.as_mut_ptr() as *mut super::State) },
+            state: unsafe { &mut *inner.command.as_mut_ptr() },
         }
     }
 }
@@ -123,7 +156,7 @@ impl<'a> Future for SlaRw<'a> {
         mut self: core::pin::Pin<&mut Self>,
         _: &mut core::task::Context<'_>,
     ) -> Poll<Self::Output> {
-        if let super::State::SlaRw(ref mut res) = self.state {
+        if let super::State::SlaRw { ref mut res, .. } = self.state {
             if let Some(res) = res.take() {
                 Poll::Ready(res)
             } else {
@@ -159,7 +192,7 @@ impl<'a, 'b> Write<'a, 'b> {
             inner.set = true;
             inner.peripheral.send_write(buf[0]);
             Self {
-                state: Some(unsafe { &mut *(inner.command.as_mut_ptr() as *mut super::State) }),
+                state: Some(unsafe { &mut *inner.command.as_mut_ptr() }),
                 _life: PhantomData,
             }
         }
@@ -198,6 +231,7 @@ impl<'a, 'b> Read<'a, 'b> {
     pub fn new<TWI: super::peripheral::TwiOps<SDA, SCL>, SDA, SCL, CLOCK>(
         inner: &'a mut super::TwiSlab<TWI, SDA, SCL, CLOCK>,
         buf: &'b mut [u8],
+        last: bool,
     ) -> Self {
         if buf.is_empty() {
             Self {
@@ -208,12 +242,13 @@ impl<'a, 'b> Read<'a, 'b> {
             inner.command.write(super::State::Read {
                 buf: buf as *mut [u8],
                 idx: 0,
+                last,
                 res: None,
             });
             inner.set = true;
-            inner.peripheral.send_read(buf.len() == 1);
+            inner.peripheral.send_read(last && buf.len() == 1);
             Self {
-                state: Some(unsafe { &mut *(inner.command.as_mut_ptr() as *mut super::State) }),
+                state: Some(unsafe { &mut *inner.command.as_mut_ptr() }),
                 _buf: buf,
             }
         }
@@ -251,11 +286,11 @@ impl<'a> Stop<'a> {
     pub fn new<TWI: super::peripheral::TwiOps<SDA, SCL>, SDA, SCL, CLOCK>(
         inner: &'a mut super::TwiSlab<TWI, SDA, SCL, CLOCK>,
     ) -> Self {
-        inner.command.write(super::State::Stop(None));
-        inner.set = true;
+        inner.command.write(super::State::Stop(Some(())));
+        inner.set = false;
         inner.peripheral.send_stop();
         Self {
-            state: unsafe { &mut *(inner.command.as_mut_ptr() as *mut super::State) },
+            state: unsafe { &mut *inner.command.as_mut_ptr() },
         }
     }
 }
@@ -1,8 +1,8 @@
 use core::{future::Future, pin::Pin, task::Poll};
 
 enum State<'a, 'b> {
-    Wait(super::Wait<'a>, u8, &'b mut [u8]),
-    Start(super::raw::Start<'a>, u8, &'b mut [u8]),
+    Wait(super::Wait<'a>, super::Address, &'b mut [u8]),
+    Start(super::raw::Start<'a>, super::Address, &'b mut [u8]),
     SlaR(super::raw::SlaRw<'a>, &'b mut [u8]),
     Read(super::raw::Read<'a, 'b>),
     Stop(super::raw::Stop<'a>, Result<(), super::Error>),
@@ -11,6 +11,7 @@ enum State<'a, 'b> {
 pub struct Read<'a, 'b, TWI: super::peripheral::TwiOps<SDA, SCL>, SDA, SCL, CLOCK> {
     twi: &'a mut super::raw::TwoWireInterface<TWI, SDA, SCL, CLOCK>,
     state: Option<State<'a, 'b>>,
+    deadline: super::Deadline,
 }
 
 impl<'a, 'b, TWI: super::peripheral::TwiOps<SDA, SCL>, SDA, SCL, CLOCK>
@@ -18,10 +19,11 @@ impl<'a, 'b, TWI: super::peripheral::TwiOps<SDA, SCL>, SDA, SCL, CLOCK>
 {
     pub(crate) fn new(
         twi: &'a mut super::raw::TwoWireInterface<TWI, SDA, SCL, CLOCK>,
-        addr: u8,
+        addr: super::Address,
         buf: &'b mut [u8],
     ) -> Self {
-        let state = {
+        // Nothing goes on the bus for an empty read, it can't end without clocking a byte.
+        let state = (!buf.is_empty()).then(|| {
             let twi =
                 unsafe { &mut *(twi as *mut super::raw::TwoWireInterface<TWI, SDA, SCL, CLOCK>) };
 
@@ -30,11 +32,12 @@ impl<'a, 'b, TWI: super::peripheral::TwiOps<SDA, SCL>, SDA, SCL, CLOCK>
             } else {
                 State::Start(twi.start(), addr, buf)
             }
-        };
+        });
 
         Self {
+            deadline: super::Deadline::new(twi),
             twi,
-            state: Some(state),
+            state,
         }
     }
 
@@ -49,9 +52,13 @@ impl<'a, 'b, TWI: super::peripheral::TwiOps<SDA, SCL>, SDA, SCL, CLOCK> Future
     type Output = Result<(), super::Error>;
 
     fn poll(self: Pin<&mut Self>, cx: &mut core::task::Context<'_>) -> Poll<Self::Output> {
-        let this = Pin::get_mut(self);
+        let this = unsafe { Pin::get_unchecked_mut(self) };
+
+        if this.state.is_none() {
+            return Poll::Ready(Ok(()));
+        }
 
-        loop {
+        let res = loop {
             match this.state.take().unwrap() {
                 State::Wait(mut fut, addr, buf) => match Pin::new(&mut fut).poll(cx) {
                     Poll::Pending => {
@@ -71,7 +78,7 @@ impl<'a, 'b, TWI: super::peripheral::TwiOps<SDA, SCL>, SDA, SCL, CLOCK> Future
                     Poll::Ready(res) => match res {
                         Ok(()) => {
                             let slar = unsafe { &mut *(this.twi_ptr()) }
-                                .sla_rw(addr, super::peripheral::Direction::Write);
+                                .sla_rw(addr, super::peripheral::Direction::Read);
                             this.state.replace(State::SlaR(slar, buf));
                         }
                         Err(err) => break Poll::Ready(Err(err)),
@@ -84,7 +91,7 @@ impl<'a, 'b, TWI: super::peripheral::TwiOps<SDA, SCL>, SDA, SCL, CLOCK> Future
                     }
                     Poll::Ready(res) => match res {
                         Ok(()) => {
-                            let read = unsafe { &mut *(this.twi_ptr()) }.read(buf);
+                            let read = unsafe { &mut *(this.twi_ptr()) }.read(buf, true);
                             this.state.replace(State::Read(read));
                         }
                         Err(err) => {
@@ -111,6 +118,13 @@ impl<'a, 'b, TWI: super::peripheral::TwiOps<SDA, SCL>, SDA, SCL, CLOCK> Future
                     Poll::Ready(()) => break Poll::Ready(res),
                 },
             }
+        };
+
+        if res.is_pending() && unsafe { Pin::new_unchecked(&mut this.deadline) }.poll_elapsed(cx) {
+            this.state = None;
+            return Poll::Ready(Err(unsafe { &mut *(this.twi_ptr()) }.recover()));
         }
+
+        res
     }
 }
@@ -0,0 +1,217 @@
+use core::{future::Future, marker::PhantomData, pin::Pin, task::Poll};
+
+use avr_device::interrupt;
+
+use super::{peripheral::Direction, Error, State, TwiSlab};
+
+/// Waits to be addressed, see [`super::TwoWireInterface::listen`].
+pub struct Listen<'a, TWI: super::peripheral::TwiOps<SDA, SCL>, SDA, SCL, CLOCK> {
+    inner: Option<&'a mut TwiSlab<TWI, SDA, SCL, CLOCK>>,
+}
+
+impl<'a, TWI: super::peripheral::TwiOps<SDA, SCL>, SDA, SCL, CLOCK>
+    Listen<'a, TWI, SDA, SCL, CLOCK>
+{
+    pub(crate) fn new(inner: &'a mut TwiSlab<TWI, SDA, SCL, CLOCK>) -> Self {
+        inner.command.write(State::Listen(None));
+        inner.set = true;
+        inner.peripheral.listen();
+        Self { inner: Some(inner) }
+    }
+}
+
+impl<'a, TWI: super::peripheral::TwiOps<SDA, SCL>, SDA, SCL, CLOCK> Future
+    for Listen<'a, TWI, SDA, SCL, CLOCK>
+{
+    type Output = Result<Request<'a, TWI, SDA, SCL, CLOCK>, Error>;
+
+    fn poll(self: Pin<&mut Self>, _: &mut core::task::Context<'_>) -> Poll<Self::Output> {
+        let this = unsafe { Pin::get_unchecked_mut(self) };
+
+        let res = interrupt::free(|_cs| {
+            let inner = this.inner.as_mut().unwrap();
+            if let State::Listen(ref mut res) = unsafe { &mut *inner.command.as_mut_ptr() } {
+                res.take()
+            } else {
+                panic!("Wrong TWI state")
+            }
+        });
+
+        match res {
+            None => Poll::Pending,
+            Some(Err(err)) => {
+                this.inner = None;
+                Poll::Ready(Err(err))
+            }
+            Some(Ok((direction, general_call))) => {
+                let inner = this.inner.take().unwrap();
+                Poll::Ready(Ok(match direction {
+                    Direction::Write => Request::Write(WriteRequest {
+                        inner: Some(inner),
+                        general_call,
+                    }),
+                    Direction::Read => Request::Read(ReadRequest { inner: Some(inner) }),
+                }))
+            }
+        }
+    }
+}
+
+impl<'a, TWI: super::peripheral::TwiOps<SDA, SCL>, SDA, SCL, CLOCK> Drop
+    for Listen<'a, TWI, SDA, SCL, CLOCK>
+{
+    fn drop(&mut self) {
+        if let Some(inner) = self.inner.take() {
+            release(inner);
+        }
+    }
+}
+
+/// A master addressed this interface, the clock is stretched until it is answered.
+pub enum Request<'a, TWI: super::peripheral::TwiOps<SDA, SCL>, SDA, SCL, CLOCK> {
+    /// The master sends data, answer with [`WriteRequest::receive`].
+    Write(WriteRequest<'a, TWI, SDA, SCL, CLOCK>),
+    /// The master wants data, answer with [`ReadRequest::respond`].
+    Read(ReadRequest<'a, TWI, SDA, SCL, CLOCK>),
+}
+
+pub struct WriteRequest<'a, TWI: super::peripheral::TwiOps<SDA, SCL>, SDA, SCL, CLOCK> {
+    inner: Option<&'a mut TwiSlab<TWI, SDA, SCL, CLOCK>>,
+    general_call: bool,
+}
+
+impl<'a, TWI: super::peripheral::TwiOps<SDA, SCL>, SDA, SCL, CLOCK>
+    WriteRequest<'a, TWI, SDA, SCL, CLOCK>
+{
+    #[inline(always)]
+    pub const fn is_general_call(&self) -> bool {
+        self.general_call
+    }
+
+    /// Receives up to `buf.len()` bytes, the byte filling `buf` is not acknowledged. Resolves to
+    /// the number of bytes received once the master stops.
+    pub fn receive<'b>(mut self, buf: &'b mut [u8]) -> Transfer<'a, 'b, TWI, SDA, SCL, CLOCK> {
+        let inner = self.inner.take().unwrap();
+        let ack = buf.len() > 1;
+
+        inner.command.write(State::SlaveReceive {
+            buf: buf as *mut [u8],
+            idx: 0,
+            res: None,
+        });
+        inner.set = true;
+        inner.peripheral.send_slave_ack(ack);
+
+        Transfer::new(inner)
+    }
+}
+
+impl<'a, TWI: super::peripheral::TwiOps<SDA, SCL>, SDA, SCL, CLOCK> Drop
+    for WriteRequest<'a, TWI, SDA, SCL, CLOCK>
+{
+    fn drop(&mut self) {
+        if let Some(inner) = self.inner.take() {
+            release(inner);
+        }
+    }
+}
+
+pub struct ReadRequest<'a, TWI: super::peripheral::TwiOps<SDA, SCL>, SDA, SCL, CLOCK> {
+    inner: Option<&'a mut TwiSlab<TWI, SDA, SCL, CLOCK>>,
+}
+
+impl<'a, TWI: super::peripheral::TwiOps<SDA, SCL>, SDA, SCL, CLOCK>
+    ReadRequest<'a, TWI, SDA, SCL, CLOCK>
+{
+    /// Sends `buf`, then 0xff if the master keeps reading. Resolves to the number of bytes of
+    /// `buf` sent once the master stops.
+    pub fn respond<'b>(mut self, buf: &'b [u8]) -> Transfer<'a, 'b, TWI, SDA, SCL, CLOCK> {
+        let inner = self.inner.take().unwrap();
+        let byte = buf.first().copied().unwrap_or(0xff);
+
+        inner.command.write(State::SlaveTransmit {
+            buf: buf as *const [u8],
+            idx: buf.len().min(1),
+            res: None,
+        });
+        inner.set = true;
+        inner.peripheral.send_slave_write(byte, buf.len() <= 1);
+
+        Transfer::new(inner)
+    }
+}
+
+impl<'a, TWI: super::peripheral::TwiOps<SDA, SCL>, SDA, SCL, CLOCK> Drop
+    for ReadRequest<'a, TWI, SDA, SCL, CLOCK>
+{
+    fn drop(&mut self) {
+        if let Some(inner) = self.inner.take() {
+            release(inner);
+        }
+    }
+}
+
+/// An answer to a [`Request`] in progress.
+pub struct Transfer<'a, 'b, TWI: super::peripheral::TwiOps<SDA, SCL>, SDA, SCL, CLOCK> {
+    inner: Option<&'a mut TwiSlab<TWI, SDA, SCL, CLOCK>>,
+    _buf: PhantomData<&'b mut [u8]>,
+}
+
+impl<'a, 'b, TWI: super::peripheral::TwiOps<SDA, SCL>, SDA, SCL, CLOCK>
+    Transfer<'a, 'b, TWI, SDA, SCL, CLOCK>
+{
+    #[inline(always)]
+    fn new(inner: &'a mut TwiSlab<TWI, SDA, SCL, CLOCK>) -> Self {
+        Self {
+            inner: Some(inner),
+            _buf: PhantomData,
+        }
+    }
+}
+
+impl<'a, 'b, TWI: super::peripheral::TwiOps<SDA, SCL>, SDA, SCL, CLOCK> Future
+    for Transfer<'a, 'b, TWI, SDA, SCL, CLOCK>
+{
+    type Output = Result<usize, Error>;
+
+    fn poll(self: Pin<&mut Self>, _: &mut core::task::Context<'_>) -> Poll<Self::Output> {
+        let this = unsafe { Pin::get_unchecked_mut(self) };
+
+        let res = interrupt::free(|_cs| {
+            let inner = this.inner.as_mut().unwrap();
+            match unsafe { &mut *inner.command.as_mut_ptr() } {
+                State::SlaveReceive { ref mut res, .. }
+                | State::SlaveTransmit { ref mut res, .. } => res.take(),
+                _ => panic!("Wrong TWI state"),
+            }
+        });
+
+        match res {
+            Some(res) => {
+                this.inner = None;
+                Poll::Ready(res)
+            }
+            None => Poll::Pending,
+        }
+    }
+}
+
+impl<'a, 'b, TWI: super::peripheral::TwiOps<SDA, SCL>, SDA, SCL, CLOCK> Drop
+    for Transfer<'a, 'b, TWI, SDA, SCL, CLOCK>
+{
+    fn drop(&mut self) {
+        if let Some(inner) = self.inner.take() {
+            release(inner);
+        }
+    }
+}
+
+/// Gives the bus back without acknowledging anything, the buffer is no longer touched.
+fn release<TWI: super::peripheral::TwiOps<SDA, SCL>, SDA, SCL, CLOCK>(
+    inner: &mut TwiSlab<TWI, SDA, SCL, CLOCK>,
+) {
+    interrupt::free(|_cs| {
+        inner.set = false;
+        inner.peripheral.release_slave(false);
+    });
+}
@@ -26,7 +26,7 @@ impl<'a, TWI: super::peripheral::TwiOps<SDA, SCL>, SDA, SCL, CLOCK> Future
         _: &mut core::task::Context<'_>,
     ) -> Poll<Self::Output> {
         let twi = self.twi.take().unwrap();
-        if twi.inner.set {
+        if !twi.inner.set {
             Poll::Ready(Transaction::new(twi))
         } else {
             self.twi.replace(twi);
@@ -53,7 +53,7 @@ impl<'a, TWI: super::peripheral::TwiOps<SDA, SCL>, SDA, SCL, CLOCK>
 
     pub fn write<'b, 'c>(
         &'b mut self,
-        addr: u8,
+        addr: super::Address,
         buf: &'c [u8],
     ) -> write::Write<'a, 'b, 'c, TWI, SDA, SCL, CLOCK> {
         write::Write::new(self, addr, buf)
@@ -61,7 +61,7 @@ impl<'a, TWI: super::peripheral::TwiOps<SDA, SCL>, SDA, SCL, CLOCK>
 
     pub fn read<'b, 'c>(
         &'b mut self,
-        addr: u8,
+        addr: super::Address,
         buf: &'c mut [u8],
     ) -> read::Read<'a, 'b, 'c, TWI, SDA, SCL, CLOCK> {
         read::Read::new(self, addr, buf)
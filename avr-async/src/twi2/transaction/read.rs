@@ -1,7 +1,11 @@
 use core::{future::Future, pin::Pin, task::Poll};
 
 enum State<'a, 'b> {
-    Start(super::super::raw::Start<'a>, u8, &'b mut [u8]),
+    Start(
+        super::super::raw::Start<'a>,
+        super::super::Address,
+        &'b mut [u8],
+    ),
     SlaR(super::super::raw::SlaRw<'a>, &'b mut [u8]),
     Read(super::super::raw::Read<'a, 'b>),
 }
@@ -9,6 +13,7 @@ enum State<'a, 'b> {
 pub struct Read<'a, 'b, 'c, TWI: super::super::peripheral::TwiOps<SDA, SCL>, SDA, SCL, CLOCK> {
     tx: &'a mut super::Transaction<'b, TWI, SDA, SCL, CLOCK>,
     state: Option<State<'b, 'c>>,
+    deadline: super::super::Deadline,
 }
 
 impl<'a, 'b, 'c, TWI: super::super::peripheral::TwiOps<SDA, SCL>, SDA, SCL, CLOCK>
@@ -16,10 +21,11 @@ impl<'a, 'b, 'c, TWI: super::super::peripheral::TwiOps<SDA, SCL>, SDA, SCL, CLOC
 {
     pub(crate) fn new(
         tx: &'a mut super::Transaction<'b, TWI, SDA, SCL, CLOCK>,
-        addr: u8,
+        addr: super::super::Address,
         buf: &'c mut [u8],
     ) -> Self {
-        let state = Some({
+        // Nothing goes on the bus for an empty read, it can't end without clocking a byte.
+        let state = (!buf.is_empty()).then(|| {
             let twi = unsafe {
                 &mut *(tx.twi as *mut super::super::raw::TwoWireInterface<TWI, SDA, SCL, CLOCK>)
             };
@@ -27,7 +33,11 @@ impl<'a, 'b, 'c, TWI: super::super::peripheral::TwiOps<SDA, SCL>, SDA, SCL, CLOC
             State::Start(start, addr, buf)
         });
 
-        Self { tx, state }
+        Self {
+            deadline: super::super::Deadline::new(tx.twi),
+            tx,
+            state,
+        }
     }
 
     fn twi_ptr(&mut self) -> *mut super::super::raw::TwoWireInterface<TWI, SDA, SCL, CLOCK> {
@@ -41,9 +51,13 @@ impl<'a, 'b, 'c, TWI: super::super::peripheral::TwiOps<SDA, SCL>, SDA, SCL, CLOC
     type Output = Result<(), super::super::Error>;
 
     fn poll(self: Pin<&mut Self>, cx: &mut core::task::Context<'_>) -> Poll<Self::Output> {
-        let this = Pin::get_mut(self);
+        let this = unsafe { Pin::get_unchecked_mut(self) };
+
+        if this.state.is_none() {
+            return Poll::Ready(Ok(()));
+        }
 
-        loop {
+        let res = loop {
             match this.state.take().unwrap() {
                 State::Start(mut fut, addr, buf) => match Pin::new(&mut fut).poll(cx) {
                     Poll::Pending => {
@@ -67,7 +81,7 @@ impl<'a, 'b, 'c, TWI: super::super::peripheral::TwiOps<SDA, SCL>, SDA, SCL, CLOC
                     }
                     Poll::Ready(res) => match res {
                         Ok(()) => {
-                            let read = unsafe { &mut *(this.twi_ptr()) }.read(buf);
+                            let read = unsafe { &mut *(this.twi_ptr()) }.read(buf, true);
                             this.state.replace(State::Read(read));
                         }
                         Err(err) => break Poll::Ready(Err(err)),
@@ -81,6 +95,14 @@ impl<'a, 'b, 'c, TWI: super::super::peripheral::TwiOps<SDA, SCL>, SDA, SCL, CLOC
                     Poll::Ready(res) => break Poll::Ready(res),
                 },
             }
+        };
+
+        if res.is_pending() && unsafe { Pin::new_unchecked(&mut this.deadline) }.poll_elapsed(cx) {
+            this.state = None;
+            this.tx.started = false;
+            return Poll::Ready(Err(unsafe { &mut *(this.twi_ptr()) }.recover()));
         }
+
+        res
     }
 }
@@ -0,0 +1,350 @@
+//! Two-wire master on the USI of the ATtiny chips, which have no TWI.
+//!
+//! The USI only shifts bits, the clock is strobed by software. A transfer is cut in phases: the
+//! START, a byte and its acknowledge bit. Every `send_*` clocks a single phase and arms the start
+//! condition and counter overflow interrupts, the driver then starts the acknowledge bit of a byte
+//! from the interrupt before reporting the result. Call [`super::TwoWireInterfaceDriver::run`]
+//! from both vectors of your `Runtime`.
+//!
+//! A slave stretching the clock for more than [`STRETCH_LIMIT`] half periods fails the phase with
+//! [`Error::BusError`]. Arbitration is not detected and there is no slave mode.
+
+use avr_hal_generic::clock::Clock;
+
+use super::peripheral::{Direction, Error, TwiOps, STRETCH_LIMIT};
+use crate::SyncUnsafeCell;
+
+// USICR
+const USISIE: u8 = 1 << 7;
+const USIOIE: u8 = 1 << 6;
+const USIWM1: u8 = 1 << 5;
+const USICS1: u8 = 1 << 3;
+const USICLK: u8 = 1 << 1;
+const USITC: u8 = 1 << 0;
+
+// USISR
+const USISIF: u8 = 1 << 7;
+const USIOIF: u8 = 1 << 6;
+const USIPF: u8 = 1 << 5;
+const USIDC: u8 = 1 << 4;
+
+/// Two-wire mode, shift register and counter clocked by the USITC strobes.
+const TWO_WIRE: u8 = USIWM1 | USICS1 | USICLK;
+/// Clears the flags, the counter overflows after 16 edges: a byte.
+const BYTE: u8 = USISIF | USIOIF | USIPF | USIDC;
+/// Clears the flags, the counter overflows after 2 edges: the acknowledge bit.
+const BIT: u8 = BYTE | 0x0e;
+
+#[derive(Clone, Copy)]
+enum Phase {
+    Idle,
+    /// START sent, reported by the start condition flag.
+    Start,
+    /// Byte shifted out, its acknowledge bit is read on the next overflow.
+    Write,
+    /// Byte shifted in, its acknowledge bit is sent on the next overflow, ACK if `true`.
+    Read(bool),
+    /// Acknowledge bit clocked, `LAST` holds the result.
+    Done,
+    /// SCL stuck low or the START didn't show up, reported by a counter overflow.
+    Failed,
+}
+
+/// Delay loop iterations for half a clock period, set by `setup`.
+static HALF_PERIOD: SyncUnsafeCell<u16> = SyncUnsafeCell::new(1);
+/// Phase waiting for its interrupt.
+static PHASE: SyncUnsafeCell<Phase> = SyncUnsafeCell::new(Phase::Idle);
+/// Result of the last phase: the received byte, or the acknowledge bit (0 for ACK).
+static LAST: SyncUnsafeCell<u8> = SyncUnsafeCell::new(0);
+
+#[inline(always)]
+fn half_period() {
+    for _ in 0..unsafe { *HALF_PERIOD.get() } {
+        unsafe { core::arch::asm!("nop") };
+    }
+}
+
+macro_rules! impl_usi {
+    (
+        $($krate:ident)::+,
+        $USI:ident,
+        $PORT:ident { $port:ident, $ddr:ident, $pin:ident },
+        $SDA:ident = $sda:literal,
+        $SCL:ident = $scl:literal
+    ) => {
+        type Usi = $($krate)::+::pac::$USI;
+
+        const SDA: u8 = 1 << $sda;
+        const SCL: u8 = 1 << $scl;
+
+        #[inline(always)]
+        fn port() -> &'static <$($krate)::+::pac::$PORT as core::ops::Deref>::Target {
+            // Only the bits of SDA and SCL are touched, the typed pins are kept by the peripheral.
+            unsafe { &*$($krate)::+::pac::$PORT::ptr() }
+        }
+
+        #[inline(always)]
+        fn set(bits: u8) {
+            port().$port.modify(|r, w| unsafe { w.bits(r.bits() | bits) });
+        }
+
+        #[inline(always)]
+        fn clear(bits: u8) {
+            port().$port.modify(|r, w| unsafe { w.bits(r.bits() & !bits) });
+        }
+
+        #[inline(always)]
+        fn output(bits: u8) {
+            port().$ddr.modify(|r, w| unsafe { w.bits(r.bits() | bits) });
+        }
+
+        #[inline(always)]
+        fn input(bits: u8) {
+            port().$ddr.modify(|r, w| unsafe { w.bits(r.bits() & !bits) });
+        }
+
+        /// Waits for SCL to go high, the slave may stretch the clock.
+        fn wait_scl() -> bool {
+            for _ in 0..STRETCH_LIMIT {
+                if port().$pin.read().bits() & SCL != 0 {
+                    return true;
+                }
+                half_period();
+            }
+            false
+        }
+
+        /// Hands the pins to the USI, both lines released.
+        fn init(usi: &Usi) {
+            set(SDA | SCL);
+            output(SDA | SCL);
+            usi.usidr.write(|w| unsafe { w.bits(0xff) });
+            usi.usicr.write(|w| unsafe { w.bits(TWO_WIRE) });
+            usi.usisr.write(|w| unsafe { w.bits(BYTE) });
+            unsafe { *PHASE.get() = Phase::Idle };
+        }
+
+        /// Waits for `phase` on the next start condition or counter overflow.
+        #[inline(always)]
+        fn arm(usi: &Usi, phase: Phase) {
+            unsafe { *PHASE.get() = phase };
+            usi.usicr
+                .write(|w| unsafe { w.bits(TWO_WIRE | USISIE | USIOIE) });
+        }
+
+        /// Fails the phase: the counter is overflowed by a software strobe, which doesn't touch
+        /// SCL, so the driver runs and reports the error.
+        fn fail(usi: &Usi) {
+            usi.usidr.write(|w| unsafe { w.bits(0xff) });
+            usi.usisr.write(|w| unsafe { w.bits(BYTE | 0x0f) });
+            usi.usicr.write(|w| unsafe { w.bits(USIWM1 | USICLK) });
+            arm(usi, Phase::Failed);
+        }
+
+        /// Strobes the clock until the counter set up by `count` overflows, returns the shifted
+        /// in data and releases SDA. `None` if a slave held SCL low for too long.
+        fn transfer(usi: &Usi, count: u8) -> Option<u8> {
+            usi.usisr.write(|w| unsafe { w.bits(count) });
+            loop {
+                half_period();
+                usi.usicr.write(|w| unsafe { w.bits(TWO_WIRE | USITC) });
+                if !wait_scl() {
+                    return None;
+                }
+                half_period();
+                usi.usicr.write(|w| unsafe { w.bits(TWO_WIRE | USITC) });
+
+                if usi.usisr.read().bits() & USIOIF != 0 {
+                    break;
+                }
+            }
+            half_period();
+
+            let data = usi.usidr.read().bits();
+            usi.usidr.write(|w| unsafe { w.bits(0xff) });
+            output(SDA);
+            Some(data)
+        }
+
+        fn write_byte(usi: &Usi, byte: u8) {
+            clear(SCL);
+            usi.usidr.write(|w| unsafe { w.bits(byte) });
+            match transfer(usi, BYTE) {
+                Some(_) => arm(usi, Phase::Write),
+                None => fail(usi),
+            }
+        }
+
+        fn read_byte(usi: &Usi, ack: bool) {
+            input(SDA);
+            match transfer(usi, BYTE) {
+                Some(byte) => {
+                    unsafe { *LAST.get() = byte };
+                    arm(usi, Phase::Read(ack));
+                }
+                None => fail(usi),
+            }
+        }
+
+        impl
+            TwiOps<
+                $($krate)::+::port::Pin<$($krate)::+::port::mode::Input, $($krate)::+::port::$SDA>,
+                $($krate)::+::port::Pin<$($krate)::+::port::mode::Input, $($krate)::+::port::$SCL>,
+            > for Usi
+        {
+            #[inline(always)]
+            fn setup<CLOCK: Clock>(&mut self, speed: u32) {
+                // A delay loop iteration takes about 4 cycles.
+                let half = (CLOCK::FREQ / speed / 8).max(1);
+                unsafe { *HALF_PERIOD.get() = half as u16 };
+                init(self);
+            }
+
+            fn send_start(&mut self) {
+                self.usisr.write(|w| unsafe { w.bits(BYTE) });
+                set(SCL);
+                if !wait_scl() {
+                    return fail(self);
+                }
+                half_period();
+                clear(SDA);
+                half_period();
+                clear(SCL);
+                set(SDA);
+
+                // SDA held low by a slave sets no flag.
+                if self.usisr.read().bits() & USISIF == 0 {
+                    return fail(self);
+                }
+                arm(self, Phase::Start);
+            }
+
+            #[inline(always)]
+            fn send_slarw(&mut self, address: u8, direction: Direction) {
+                let dirbit = if direction == Direction::Read { 1 } else { 0 };
+                write_byte(self, (address << 1) | dirbit);
+            }
+
+            #[inline(always)]
+            fn send_write(&mut self, byte: u8) {
+                write_byte(self, byte);
+            }
+
+            #[inline(always)]
+            fn send_read(&mut self, last: bool) {
+                read_byte(self, !last);
+            }
+
+            fn send_stop(&mut self) {
+                self.usicr.write(|w| unsafe { w.bits(TWO_WIRE) });
+                unsafe { *PHASE.get() = Phase::Idle };
+
+                clear(SDA);
+                set(SCL);
+                // Still held low, SDA is released anyway and the bus is left to the timeout.
+                wait_scl();
+                half_period();
+                set(SDA);
+                half_period();
+            }
+
+            fn is_ready(&mut self) -> bool {
+                let flags = self.usisr.read().bits();
+                match unsafe { *PHASE.get() } {
+                    Phase::Idle => false,
+                    Phase::Start => flags & USISIF != 0,
+                    Phase::Write if flags & USIOIF != 0 => {
+                        input(SDA);
+                        match transfer(self, BIT) {
+                            Some(bit) => {
+                                unsafe { *LAST.get() = bit & 1 };
+                                arm(self, Phase::Done);
+                            }
+                            None => fail(self),
+                        }
+                        false
+                    }
+                    Phase::Read(ack) if flags & USIOIF != 0 => {
+                        self.usidr
+                            .write(|w| unsafe { w.bits(if ack { 0x00 } else { 0xff }) });
+                        match transfer(self, BIT) {
+                            Some(_) => arm(self, Phase::Done),
+                            None => fail(self),
+                        }
+                        false
+                    }
+                    _ => flags & USIOIF != 0,
+                }
+            }
+
+            #[inline(always)]
+            fn recv_start(&mut self) -> Result<(), Error> {
+                match unsafe { *PHASE.get() } {
+                    Phase::Start => Ok(()),
+                    _ => Err(Error::BusError),
+                }
+            }
+
+            #[inline(always)]
+            fn recv_slarw(&mut self) -> Result<(), Error> {
+                match unsafe { (*PHASE.get(), *LAST.get()) } {
+                    (Phase::Done, 0) => Ok(()),
+                    (Phase::Done, _) => Err(Error::AddressNack),
+                    _ => Err(Error::BusError),
+                }
+            }
+
+            #[inline(always)]
+            fn recv_write(&mut self) -> Result<(), Error> {
+                match unsafe { (*PHASE.get(), *LAST.get()) } {
+                    (Phase::Done, 0) => Ok(()),
+                    (Phase::Done, _) => Err(Error::DataNack),
+                    _ => Err(Error::BusError),
+                }
+            }
+
+            #[inline(always)]
+            fn recv_read(&mut self) -> Result<u8, Error> {
+                match unsafe { *PHASE.get() } {
+                    Phase::Done => Ok(unsafe { *LAST.get() }),
+                    _ => Err(Error::BusError),
+                }
+            }
+
+            #[inline(always)]
+            fn disable(&mut self) {
+                self.usicr.write(|w| unsafe { w.bits(TWO_WIRE) });
+            }
+
+            #[inline(always)]
+            fn set_enabled(&mut self, enabled: bool) {
+                if enabled {
+                    init(self);
+                } else {
+                    self.usicr.write(|w| unsafe { w.bits(0) });
+                    // `init` left both lines driven high.
+                    input(SDA | SCL);
+                }
+            }
+
+        }
+    };
+}
+
+#[cfg(any(feature = "attiny85", feature = "attiny167"))]
+impl_usi!(
+    crate::hal,
+    USI,
+    PORTB { portb, ddrb, pinb },
+    PB0 = 0,
+    PB2 = 2
+);
+
+#[cfg(feature = "attiny84")]
+impl_usi!(
+    crate::hal,
+    USI,
+    PORTA { porta, ddra, pina },
+    PA6 = 6,
+    PA4 = 4
+);
@@ -1,8 +1,8 @@
 use core::{future::Future, pin::Pin, task::Poll};
 
 enum State<'a, 'b> {
-    Wait(super::Wait<'a>, u8, &'b [u8]),
-    Start(super::raw::Start<'a>, u8, &'b [u8]),
+    Wait(super::Wait<'a>, super::Address, &'b [u8]),
+    Start(super::raw::Start<'a>, super::Address, &'b [u8]),
     SlaW(super::raw::SlaRw<'a>, &'b [u8]),
     Write(super::raw::Write<'a, 'b>),
     Stop(super::raw::Stop<'a>, Result<(), super::Error>),
@@ -11,6 +11,7 @@ enum State<'a, 'b> {
 pub struct Write<'a, 'b, TWI: super::peripheral::TwiOps<SDA, SCL>, SDA, SCL, CLOCK> {
     twi: &'a mut super::raw::TwoWireInterface<TWI, SDA, SCL, CLOCK>,
     state: Option<State<'a, 'b>>,
+    deadline: super::Deadline,
 }
 
 impl<'a, 'b, TWI: super::peripheral::TwiOps<SDA, SCL>, SDA, SCL, CLOCK>
@@ -18,7 +19,7 @@ impl<'a, 'b, TWI: super::peripheral::TwiOps<SDA, SCL>, SDA, SCL, CLOCK>
 {
     pub(crate) fn new(
         twi: &'a mut super::raw::TwoWireInterface<TWI, SDA, SCL, CLOCK>,
-        addr: u8,
+        addr: super::Address,
         buf: &'b [u8],
     ) -> Self {
         let state = {
@@ -33,6 +34,7 @@ impl<'a, 'b, TWI: super::peripheral::TwiOps<SDA, SCL>, SDA, SCL, CLOCK>
         };
 
         Self {
+            deadline: super::Deadline::new(twi),
             twi,
             state: Some(state),
         }
@@ -49,9 +51,9 @@ impl<'a, 'b, TWI: super::peripheral::TwiOps<SDA, SCL>, SDA, SCL, CLOCK> Future
     type Output = Result<(), super::Error>;
 
     fn poll(self: Pin<&mut Self>, cx: &mut core::task::Context<'_>) -> Poll<Self::Output> {
-        let this = Pin::get_mut(self);
+        let this = unsafe { Pin::get_unchecked_mut(self) };
 
-        loop {
+        let res = loop {
             match this.state.take().unwrap() {
                 State::Wait(mut fut, addr, buf) => match Pin::new(&mut fut).poll(cx) {
                     Poll::Pending => {
@@ -88,9 +90,6 @@ impl<'a, 'b, TWI: super::peripheral::TwiOps<SDA, SCL>, SDA, SCL, CLOCK> Future
                             this.state.replace(State::Write(write));
                         }
                         Err(err) => {
-                            if err == super::Error::AddressNack {
-                                crate::led2();
-                            }
                             let stop = unsafe { &mut *(this.twi_ptr()) }.stop();
                             this.state.replace(State::Stop(stop, Err(err)));
                         }
@@ -114,6 +113,13 @@ impl<'a, 'b, TWI: super::peripheral::TwiOps<SDA, SCL>, SDA, SCL, CLOCK> Future
                     Poll::Ready(()) => break Poll::Ready(res),
                 },
             }
+        };
+
+        if res.is_pending() && unsafe { Pin::new_unchecked(&mut this.deadline) }.poll_elapsed(cx) {
+            this.state = None;
+            return Poll::Ready(Err(unsafe { &mut *(this.twi_ptr()) }.recover()));
         }
+
+        res
     }
 }
@@ -11,7 +11,7 @@ use avr_async::{
     main, r#yield,
     reexports::avr_hal_generic::clock,
     slab::Slab,
-    twi::{TwoWireInterface1, TwoWireInterfaceDriver1},
+    twi2::{Address, TwoWireInterface1, TwoWireInterfaceDriver1},
 };
 
 mod util;
@@ -42,7 +42,7 @@ impl avr_async::runtime::Runtime for Runtime {
 
         let (twi, driver) = TwoWireInterface1::new(
             slab,
-            avr_async::twi!(peripherals, pins, clock::MHz16, 50_000),
+            avr_async::two_wire!(peripherals, pins, clock::MHz16, 50_000),
         );
 
         (
@@ -100,7 +100,10 @@ fn blink2() {
 async fn main(mut twi: TwoWireInterface1<MHz16>) {
     let on = true;
     if twi
-        .write(0x3c, &[0x40, 0xAE | if on { 1 } else { 0 }])
+        .write(
+            Address::const_new(0x3c),
+            &[0x40, 0xAE | if on { 1 } else { 0 }],
+        )
         .await
         .is_err()
     {